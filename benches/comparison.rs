@@ -1313,5 +1313,63 @@ fn bench_single(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_many, bench_single);
+fn bench_templates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Templates");
+    let inputs = [
+        "??$fun_tmpl_recurse@H$1??$fun_tmpl_recurse@H$1?ident@fn_space@@YA?AURetVal@2@H@Z@fn_space@@YA?AURetVal@1@H@Z@fn_space@@YA?AURetVal@0@H@Z",
+        "??$fun_tmpl_recurse@H$1?ident@fn_space@@YA?AURetVal@2@H@Z@fn_space@@YA?AURetVal@0@H@Z",
+        "?foo_abbb@@YAXV?$A@V?$B@D@@V1@V1@@@@Z",
+        "?template_template_fun@@YAXU?$Type@U?$Thing@USecond@@$00@@USecond@@@@@Z",
+    ];
+
+    group.bench_with_input("undname", &inputs, |b, inputs| {
+        b.iter(|| {
+            let mut output = String::default();
+            for &input in inputs {
+                _ = undname::demangle_into(input, Flags::empty(), &mut output);
+                hint::black_box(&output);
+            }
+        });
+    });
+
+    group.bench_with_input("msvc_demangler", &inputs, |b, inputs| {
+        b.iter(|| {
+            for &input in inputs {
+                let output = msvc_demangler::demangle(input, DemangleFlags::empty());
+                hint::black_box(&output);
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_capacity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Capacity");
+    let input = format!("?{}@3HA", "a@".repeat(0x200));
+
+    group.bench_with_input("demangle", &input, |b, i| {
+        b.iter(|| {
+            let output = undname::demangle(i, Flags::empty()).unwrap();
+            hint::black_box(&output);
+        });
+    });
+
+    group.bench_with_input("demangle_with_capacity", &input, |b, i| {
+        b.iter(|| {
+            let output = undname::demangle_with_capacity(i, Flags::empty(), i.len() * 4).unwrap();
+            hint::black_box(&output);
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_many,
+    bench_single,
+    bench_templates,
+    bench_capacity
+);
 criterion_main!(benches);
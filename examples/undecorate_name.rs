@@ -48,6 +48,75 @@ struct Cli {
 
     #[arg(long)]
     name_only: bool,
+
+    #[arg(long)]
+    size_t_aliases: bool,
+
+    #[arg(long)]
+    no_arguments: bool,
+
+    #[arg(long)]
+    strict_utf8_identifiers: bool,
+
+    #[arg(long)]
+    build_stable_names: bool,
+
+    #[arg(long)]
+    no_conversion_operator_target: bool,
+
+    #[arg(long)]
+    split_nested_name_separator: bool,
+
+    #[arg(long)]
+    drop_void_return: bool,
+
+    #[arg(long)]
+    canonical_integer_literals: bool,
+
+    #[arg(long)]
+    llvm_compat: bool,
+
+    #[arg(long)]
+    show_function_pointer_params_inline: bool,
+
+    #[arg(long)]
+    no_rtti_quote_decoration: bool,
+
+    #[arg(long)]
+    render_this_parameter: bool,
+
+    #[arg(long)]
+    flatten_nested_templates: bool,
+
+    #[arg(long)]
+    emit_tag_in_template_args: bool,
+
+    #[arg(long)]
+    numeric_scope_from_zero: bool,
+
+    #[arg(long)]
+    stable_lambda_numbers: bool,
+
+    #[arg(long)]
+    west_const: bool,
+
+    #[arg(long)]
+    abbreviate_std: bool,
+
+    #[arg(long)]
+    char8_as_unsigned_char: bool,
+
+    #[arg(long)]
+    trailing_const_on_methods_only: bool,
+
+    #[arg(long)]
+    validate_string_crc: bool,
+
+    #[arg(long)]
+    no_auto_placeholder: bool,
+
+    #[arg(long)]
+    no_cc_on_data: bool,
 }
 
 fn main() {
@@ -84,6 +153,75 @@ fn main() {
         if cli.name_only {
             flags |= Flags::NAME_ONLY;
         }
+        if cli.size_t_aliases {
+            flags |= Flags::SIZE_T_ALIASES;
+        }
+        if cli.no_arguments {
+            flags |= Flags::NO_ARGUMENTS;
+        }
+        if cli.strict_utf8_identifiers {
+            flags |= Flags::STRICT_UTF8_IDENTIFIERS;
+        }
+        if cli.build_stable_names {
+            flags |= Flags::BUILD_STABLE_NAMES;
+        }
+        if cli.no_conversion_operator_target {
+            flags |= Flags::NO_CONVERSION_OPERATOR_TARGET;
+        }
+        if cli.split_nested_name_separator {
+            flags |= Flags::SPLIT_NESTED_NAME_SEPARATOR;
+        }
+        if cli.drop_void_return {
+            flags |= Flags::DROP_VOID_RETURN;
+        }
+        if cli.canonical_integer_literals {
+            flags |= Flags::CANONICAL_INTEGER_LITERALS;
+        }
+        if cli.llvm_compat {
+            flags |= Flags::LLVM_COMPAT;
+        }
+        if cli.show_function_pointer_params_inline {
+            flags |= Flags::SHOW_FUNCTION_POINTER_PARAMS_INLINE;
+        }
+        if cli.no_rtti_quote_decoration {
+            flags |= Flags::NO_RTTI_QUOTE_DECORATION;
+        }
+        if cli.render_this_parameter {
+            flags |= Flags::RENDER_THIS_PARAMETER;
+        }
+        if cli.flatten_nested_templates {
+            flags |= Flags::FLATTEN_NESTED_TEMPLATES;
+        }
+        if cli.emit_tag_in_template_args {
+            flags |= Flags::EMIT_TAG_IN_TEMPLATE_ARGS;
+        }
+        if cli.numeric_scope_from_zero {
+            flags |= Flags::NUMERIC_SCOPE_FROM_ZERO;
+        }
+        if cli.stable_lambda_numbers {
+            flags |= Flags::STABLE_LAMBDA_NUMBERS;
+        }
+        if cli.west_const {
+            flags |= Flags::WEST_CONST;
+        }
+        if cli.abbreviate_std {
+            flags |= Flags::ABBREVIATE_STD;
+        }
+        if cli.char8_as_unsigned_char {
+            flags |= Flags::CHAR8_AS_UNSIGNED_CHAR;
+        }
+        if cli.trailing_const_on_methods_only {
+            flags |= Flags::TRAILING_CONST_ON_METHODS_ONLY;
+        }
+        if cli.validate_string_crc {
+            flags |= Flags::VALIDATE_STRING_CRC;
+        }
+        if cli.no_auto_placeholder {
+            flags |= Flags::NO_AUTO_PLACEHOLDER;
+        }
+        if cli.no_cc_on_data {
+            flags |= Flags::NO_CC_ON_DATA;
+        }
         flags
     };
 
@@ -38,6 +38,7 @@ use crate::{
         NamedIdentifierNode,
         Node,
         NodeArrayNode,
+        PassThroughSymbolNode,
         PointerTypeNode,
         PrimitiveTypeNode,
         QualifiedNameNode,
@@ -88,6 +89,7 @@ pub(crate) enum NodeStorage<'alloc> {
     IntegerLiteral(&'alloc mut IntegerLiteralNode),
 
     Md5Symbol(&'alloc mut Md5SymbolNode),
+    PassThroughSymbol(&'alloc mut PassThroughSymbolNode),
     SpecialTableSymbol(&'alloc mut SpecialTableSymbolNode),
     LocalStaticGuardVariable(&'alloc mut LocalStaticGuardVariableNode),
     EncodedStringLiteral(&'alloc mut EncodedStringLiteralNode<'alloc>),
@@ -129,6 +131,7 @@ impl_into_storage!(TemplateParameterReferenceNode => TemplateParameterReference)
 impl_into_storage!(IntegerLiteralNode => IntegerLiteral);
 
 impl_into_storage!(Md5SymbolNode => Md5Symbol);
+impl_into_storage!(PassThroughSymbolNode => PassThroughSymbol);
 impl_into_storage!(SpecialTableSymbolNode => SpecialTableSymbol);
 impl_into_storage!(LocalStaticGuardVariableNode => LocalStaticGuardVariable);
 impl_into_storage!(EncodedStringLiteralNode<'alloc> => EncodedStringLiteral);
@@ -195,6 +198,7 @@ impl_from_storage!(TemplateParameterReference);
 impl_from_storage!(IntegerLiteral);
 
 impl_from_storage!(Md5Symbol);
+impl_from_storage!(PassThroughSymbol);
 impl_from_storage!(SpecialTableSymbol);
 impl_from_storage!(LocalStaticGuardVariable);
 impl_from_storage!(EncodedStringLiteral);
@@ -252,6 +256,7 @@ impl_from_storage_interface!(
         TemplateParameterReference,
         IntegerLiteral,
         Md5Symbol,
+        PassThroughSymbol,
         SpecialTableSymbol,
         LocalStaticGuardVariable,
         EncodedStringLiteral,
@@ -291,6 +296,7 @@ impl_from_storage_interface!(
 impl_from_storage_interface!(
     ISymbolNode = [
         Md5Symbol,
+        PassThroughSymbol,
         SpecialTableSymbol,
         LocalStaticGuardVariable,
         EncodedStringLiteral,
@@ -310,6 +316,12 @@ impl<'alloc> NodeCache<'alloc> {
         }
     }
 
+    #[cfg(feature = "stats")]
+    #[must_use]
+    pub(crate) fn len(&self) -> usize {
+        self.storage.len()
+    }
+
     pub(crate) fn intern<T>(
         &mut self,
         node: T,
@@ -429,6 +441,7 @@ impl_upcast!(IntegerLiteral => INode);
 
 impl_upcast!(ISymbolNode => INode);
 impl_upcast!(Md5Symbol => INode);
+impl_upcast!(PassThroughSymbol => INode);
 impl_upcast!(SpecialTableSymbol => INode);
 impl_upcast!(LocalStaticGuardVariable => INode);
 impl_upcast!(EncodedStringLiteral => INode);
@@ -458,6 +471,7 @@ impl_upcast!(StructorIdentifier => IIdentifierNode);
 impl_upcast!(RttiBaseClassDescriptor => IIdentifierNode);
 
 impl_upcast!(Md5Symbol => ISymbolNode);
+impl_upcast!(PassThroughSymbol => ISymbolNode);
 impl_upcast!(SpecialTableSymbol => ISymbolNode);
 impl_upcast!(LocalStaticGuardVariable => ISymbolNode);
 impl_upcast!(EncodedStringLiteral => ISymbolNode);
@@ -616,6 +630,7 @@ impl_node_handle!(TemplateParameterReference => TemplateParameterReferenceNode);
 impl_node_handle!(IntegerLiteral => IntegerLiteralNode);
 
 impl_node_handle!(Md5Symbol => Md5SymbolNode);
+impl_node_handle!(PassThroughSymbol => PassThroughSymbolNode);
 impl_node_handle!(SpecialTableSymbol => SpecialTableSymbolNode);
 impl_node_handle!(LocalStaticGuardVariable => LocalStaticGuardVariableNode);
 impl_node_handle!(EncodedStringLiteral => EncodedStringLiteralNode<'alloc>);
@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "debug")]
+use crate::Backrefs;
+#[cfg(feature = "stats")]
+use crate::DemangleStats;
 use crate::{
     alloc,
     cache::{
@@ -28,6 +32,7 @@ use crate::{
         NodeArray,
         NodeCache,
         NodeHandle,
+        PassThroughSymbol,
         PointerType,
         PrimitiveType,
         QualifiedName,
@@ -65,6 +70,7 @@ use crate::{
         Md5SymbolNode,
         NamedIdentifierNode,
         NodeArrayNode,
+        PassThroughSymbolNode,
         PointerAffinity,
         PointerTypeNode,
         PrimitiveKind,
@@ -76,6 +82,7 @@ use crate::{
         SpecialTableSymbolNode,
         StorageClass,
         StructorIdentifierNode,
+        SymbolNode,
         TagKind,
         TagTypeNode,
         TemplateParameterReferenceNode,
@@ -84,36 +91,61 @@ use crate::{
         VariableSymbolName,
         VariableSymbolNode,
         VcallThunkIdentifierNode,
-        WriteableNode as _,
+        WriteableNode,
     },
+    Access,
+    ComparisonCategory,
     Error,
+    FunctionAttributes,
     OutputFlags,
     Result,
+    RttiKind,
+    StructorKind,
+    SymbolInfo,
+    SymbolKind,
+    VariableNameKind,
     Writer,
+    MAX_NAME_BACKREFS,
+    MAX_PARAM_BACKREFS,
 };
 use arrayvec::ArrayVec;
 use bumpalo::Bump;
 use smallvec::SmallVec;
 use std::{
+    fmt,
     io,
-    io::Write as _,
     mem,
+    str,
 };
 
 mod writing {
     use crate::{
         Error,
+        Result,
         Writer,
     };
     use bumpalo::collections::Vec as BumpVec;
     use std::{
+        fmt,
         io,
         str::Utf8Error,
     };
 
-    pub(super) trait Buffer: io::Write {
+    fn check_output_len(len_bytes: usize, additional: usize) -> Result<()> {
+        let final_len = additional.checked_add(len_bytes);
+        if matches!(final_len, Some(x) if x < (1 << 20)) {
+            Ok(())
+        } else {
+            // a demangled string that's over a mb in length? bail
+            Err(Error::MaliciousInput)
+        }
+    }
+
+    pub(crate) trait Buffer {
         fn as_bytes(&self) -> &[u8];
 
+        fn extend_from_slice(&mut self, bytes: &[u8]);
+
         fn len_bytes(&self) -> usize {
             self.as_bytes().len()
         }
@@ -130,44 +162,37 @@ mod writing {
         fn as_bytes(&self) -> &[u8] {
             self.as_slice()
         }
+
+        fn extend_from_slice(&mut self, bytes: &[u8]) {
+            Vec::extend_from_slice(self, bytes);
+        }
     }
 
     impl Buffer for BumpVec<'_, u8> {
         fn as_bytes(&self) -> &[u8] {
             self.as_slice()
         }
+
+        fn extend_from_slice(&mut self, bytes: &[u8]) {
+            BumpVec::extend_from_slice(self, bytes);
+        }
     }
 
-    pub(super) struct BufWriter<B: Buffer> {
+    pub(crate) struct BufWriter<B: Buffer> {
         buffer: B,
     }
 
     impl<B: Buffer> BufWriter<B> {
-        pub(super) fn new(buffer: B) -> Self {
+        pub(crate) fn new(buffer: B) -> Self {
             Self { buffer }
         }
 
-        pub(super) fn into_bytes(self) -> B {
-            self.buffer
-        }
-    }
-
-    impl<B: Buffer> io::Write for BufWriter<B> {
-        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-            let final_len = buf.len().checked_add(self.buffer.len_bytes());
-            if matches!(final_len, Some(x) if x < (1 << 20)) {
-                self.buffer.write(buf)
-            } else {
-                // a demangled string that's over a mb in length? bail
-                Err(io::Error::new(
-                    io::ErrorKind::OutOfMemory,
-                    Error::MaliciousInput,
-                ))
-            }
+        pub(super) fn as_bytes(&self) -> &[u8] {
+            self.buffer.as_bytes()
         }
 
-        fn flush(&mut self) -> io::Result<()> {
-            self.buffer.flush()
+        pub(crate) fn into_bytes(self) -> B {
+            self.buffer
         }
     }
 
@@ -182,6 +207,12 @@ mod writing {
     }
 
     impl<B: Buffer> Writer for BufWriter<B> {
+        fn write_str(&mut self, s: &str) -> Result<()> {
+            check_output_len(self.buffer.len_bytes(), s.len())?;
+            self.buffer.extend_from_slice(s.as_bytes());
+            Ok(())
+        }
+
         fn last_char(&self) -> Option<char> {
             self.buffer.last_char()
         }
@@ -190,17 +221,357 @@ mod writing {
             self.buffer.len_bytes()
         }
     }
+
+    pub(super) trait Stream {
+        fn write_str(&mut self, s: &str) -> Result<()>;
+    }
+
+    pub(super) struct FmtStream<'a, W: fmt::Write + ?Sized>(pub(super) &'a mut W);
+
+    impl<W: fmt::Write + ?Sized> Stream for FmtStream<'_, W> {
+        fn write_str(&mut self, s: &str) -> Result<()> {
+            self.0.write_str(s)?;
+            Ok(())
+        }
+    }
+
+    pub(super) struct IoStream<'a, W: io::Write + ?Sized>(pub(super) &'a mut W);
+
+    impl<W: io::Write + ?Sized> Stream for IoStream<'_, W> {
+        fn write_str(&mut self, s: &str) -> Result<()> {
+            self.0.write_all(s.as_bytes())?;
+            Ok(())
+        }
+    }
+
+    // Forwards output directly to a stream, without any intermediate buffering.
+    pub(super) struct StreamWriter<S: Stream> {
+        stream: S,
+        last_char: Option<char>,
+        len_bytes: usize,
+    }
+
+    impl<S: Stream> StreamWriter<S> {
+        pub(super) fn new(stream: S) -> Self {
+            Self {
+                stream,
+                last_char: None,
+                len_bytes: 0,
+            }
+        }
+    }
+
+    impl<S: Stream> Writer for StreamWriter<S> {
+        fn write_str(&mut self, s: &str) -> Result<()> {
+            check_output_len(self.len_bytes, s.len())?;
+            self.stream.write_str(s)?;
+            self.len_bytes += s.len();
+            if let Some(c) = s.chars().next_back() {
+                self.last_char = Some(c);
+            }
+            Ok(())
+        }
+
+        fn last_char(&self) -> Option<char> {
+            self.last_char
+        }
+
+        fn len_bytes(&self) -> usize {
+            self.len_bytes
+        }
+    }
 }
 
-use writing::BufWriter;
+pub(crate) use writing::BufWriter;
+use writing::{
+    FmtStream,
+    IoStream,
+    StreamWriter,
+};
 
-#[derive(Default)]
+mod rust_v0 {
+    use crate::mangled_string::MangledString;
+
+    // Symbols nest at least one character per level, but bound the recursion anyway.
+    const MAX_DEPTH: usize = 256;
+
+    // A recognizer for the grammar of Rust's v0 symbol mangling scheme:
+    //   _R [<decimal-number>] <path> [<instantiating-crate>] [<vendor-specific-suffix>]
+    // Names are only checked for well-formedness, and are never decoded.
+    struct Parser<'string> {
+        input: MangledString<'string>,
+        depth: usize,
+    }
+
+    pub(super) fn is_symbol(mangled_name: &str) -> bool {
+        let Some(rest) = mangled_name.strip_prefix("_R") else {
+            return false;
+        };
+        let mut parser = Parser {
+            input: MangledString::new(rest),
+            depth: 0,
+        };
+        parser.symbol().is_some()
+    }
+
+    impl Parser<'_> {
+        fn symbol(&mut self) -> Option<()> {
+            if self.input.first_char()?.is_ascii_digit() {
+                self.decimal_number()?;
+            }
+            self.path()?;
+            if !self.is_at_end() {
+                self.path()?;
+            }
+            self.is_at_end().then_some(())
+        }
+
+        fn is_at_end(&self) -> bool {
+            // anything after a vendor-specific suffix is opaque
+            self.input.is_empty() || self.input.starts_with(".") || self.input.starts_with("$")
+        }
+
+        fn nested<F>(&mut self, f: F) -> Option<()>
+        where
+            F: FnOnce(&mut Self) -> Option<()>,
+        {
+            if self.depth == MAX_DEPTH {
+                return None;
+            }
+            self.depth += 1;
+            let result = f(self);
+            self.depth -= 1;
+            result
+        }
+
+        fn path(&mut self) -> Option<()> {
+            self.nested(|this| match this.input.try_consume()? {
+                'C' => this.identifier(),
+                'M' => {
+                    this.impl_path()?;
+                    this.r#type()
+                }
+                'X' => {
+                    this.impl_path()?;
+                    this.r#type()?;
+                    this.path()
+                }
+                'Y' => {
+                    this.r#type()?;
+                    this.path()
+                }
+                'N' => {
+                    this.input.try_consume_char_if(char::is_ascii_alphabetic)?;
+                    this.path()?;
+                    this.identifier()
+                }
+                'I' => {
+                    this.path()?;
+                    this.list(Self::generic_arg)
+                }
+                'B' => this.base_62_number(),
+                _ => None,
+            })
+        }
+
+        fn impl_path(&mut self) -> Option<()> {
+            self.disambiguator()?;
+            self.path()
+        }
+
+        fn identifier(&mut self) -> Option<()> {
+            self.disambiguator()?;
+            self.undisambiguated_identifier()
+        }
+
+        fn undisambiguated_identifier(&mut self) -> Option<()> {
+            // 'u' marks a Punycode encoded identifier, which is still plain ASCII
+            self.input.try_consume_char('u');
+            let len = self.decimal_number()?;
+            // '_' separates the length from identifiers starting with a digit or '_'
+            if self.input.try_consume_char('_').is_some()
+                && !self
+                    .input
+                    .first_char()
+                    .is_some_and(|x| x.is_ascii_digit() || x == '_')
+            {
+                return None;
+            }
+            self.input
+                .try_consume_n_bytes(len)?
+                .bytes()
+                .all(|x| x.is_ascii_alphanumeric() || x == b'_')
+                .then_some(())
+        }
+
+        fn disambiguator(&mut self) -> Option<()> {
+            if self.input.try_consume_char('s').is_some() {
+                self.base_62_number()?;
+            }
+            Some(())
+        }
+
+        fn generic_arg(&mut self) -> Option<()> {
+            if self.input.try_consume_char('L').is_some() {
+                self.base_62_number()
+            } else if self.input.try_consume_char('K').is_some() {
+                self.r#const()
+            } else {
+                self.r#type()
+            }
+        }
+
+        fn r#type(&mut self) -> Option<()> {
+            self.nested(|this| match this.input.first_char()? {
+                'a' | 'b' | 'c' | 'd' | 'e' | 'f' | 'h' | 'i' | 'j' | 'l' | 'm' | 'n' | 'o'
+                | 'p' | 's' | 't' | 'u' | 'v' | 'x' | 'y' | 'z' => {
+                    this.input.try_consume();
+                    Some(())
+                }
+                'A' => {
+                    this.input.try_consume();
+                    this.r#type()?;
+                    this.r#const()
+                }
+                'S' | 'P' | 'O' => {
+                    this.input.try_consume();
+                    this.r#type()
+                }
+                'R' | 'Q' => {
+                    this.input.try_consume();
+                    if this.input.try_consume_char('L').is_some() {
+                        this.base_62_number()?;
+                    }
+                    this.r#type()
+                }
+                'F' => {
+                    this.input.try_consume();
+                    this.binder()?;
+                    this.input.try_consume_char('U');
+                    if this.input.try_consume_char('K').is_some()
+                        && this.input.try_consume_char('C').is_none()
+                    {
+                        this.undisambiguated_identifier()?;
+                    }
+                    this.list(Self::r#type)?;
+                    this.r#type()
+                }
+                'D' => {
+                    this.input.try_consume();
+                    this.binder()?;
+                    this.list(Self::dyn_trait)?;
+                    this.input.try_consume_char('L')?;
+                    this.base_62_number()
+                }
+                'T' => {
+                    this.input.try_consume();
+                    this.list(Self::r#type)
+                }
+                _ => this.path(),
+            })
+        }
+
+        fn binder(&mut self) -> Option<()> {
+            if self.input.try_consume_char('G').is_some() {
+                self.base_62_number()?;
+            }
+            Some(())
+        }
+
+        fn dyn_trait(&mut self) -> Option<()> {
+            self.path()?;
+            while self.input.try_consume_char('p').is_some() {
+                self.undisambiguated_identifier()?;
+                self.r#type()?;
+            }
+            Some(())
+        }
+
+        fn r#const(&mut self) -> Option<()> {
+            self.nested(|this| match this.input.try_consume()? {
+                'p' => Some(()),
+                'B' => this.base_62_number(),
+                'R' | 'Q' => this.r#const(),
+                'A' | 'T' => this.list(Self::r#const),
+                'V' => {
+                    this.path()?;
+                    match this.input.try_consume()? {
+                        'U' => Some(()),
+                        'T' => this.list(Self::r#const),
+                        'S' => this.list(|this| {
+                            this.identifier()?;
+                            this.r#const()
+                        }),
+                        _ => None,
+                    }
+                }
+                'a' | 'b' | 'c' | 'e' | 'h' | 'i' | 'j' | 'l' | 'm' | 'n' | 'o' | 's' | 't'
+                | 'x' | 'y' => {
+                    this.input.try_consume_char('n');
+                    while this
+                        .input
+                        .try_consume_char_if(char::is_ascii_hexdigit)
+                        .is_some()
+                    {}
+                    this.input.try_consume_char('_').map(|_| ())
+                }
+                _ => None,
+            })
+        }
+
+        // Parses elements until the terminating 'E'.
+        fn list<F>(&mut self, mut f: F) -> Option<()>
+        where
+            F: FnMut(&mut Self) -> Option<()>,
+        {
+            while self.input.try_consume_char('E').is_none() {
+                f(self)?;
+            }
+            Some(())
+        }
+
+        fn base_62_number(&mut self) -> Option<()> {
+            while self
+                .input
+                .try_consume_char_if(char::is_ascii_alphanumeric)
+                .is_some()
+            {}
+            self.input.try_consume_char('_').map(|_| ())
+        }
+
+        fn decimal_number(&mut self) -> Option<usize> {
+            if self.input.try_consume_char('0').is_some() {
+                return Some(0);
+            }
+            let mut result = 0usize;
+            let mut digits = 0;
+            while let Some(c) = self.input.try_consume_char_if(char::is_ascii_digit) {
+                result = result
+                    .checked_mul(10)?
+                    .checked_add((c as u8 - b'0').into())?;
+                digits += 1;
+            }
+            (digits > 0).then_some(result)
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 struct BackrefContext {
-    function_params: ArrayVec<NodeHandle<ITypeNode>, 10>,
+    function_params: ArrayVec<NodeHandle<ITypeNode>, MAX_PARAM_BACKREFS>,
 
     // The first 10 BackReferences in a mangled name can be back-referenced by
     // special name @[0-9]. This is a storage for the first 10 BackReferences.
-    names: ArrayVec<NodeHandle<NamedIdentifier>, 10>,
+    names: ArrayVec<NodeHandle<NamedIdentifier>, MAX_NAME_BACKREFS>,
+}
+
+#[cfg(feature = "stats")]
+#[derive(Default)]
+struct StatsContext {
+    depth: usize,
+    max_depth: usize,
+    max_name_backrefs: usize,
+    max_param_backrefs: usize,
 }
 
 #[derive(Clone, Copy)]
@@ -244,6 +615,21 @@ pub(crate) struct Demangler<'alloc, 'string: 'alloc> {
     cache: NodeCache<'alloc>,
     backrefs: BackrefContext,
     flags: OutputFlags,
+
+    // A note about the symbol which is printed before it, e.g. for instrumentation
+    // symbols that wrap a regular mangled name.
+    annotation: Option<&'static str>,
+
+    // Whether the symbol is instrumentation data which was emitted for the symbol
+    // it names, rather than being that symbol itself.
+    is_instrumentation: bool,
+
+    // A reusable buffer for names which are rendered only to be compared against
+    // or copied into the arena, so that we don't grow the arena with every render.
+    scratch: Vec<u8>,
+
+    #[cfg(feature = "stats")]
+    stats: StatsContext,
 }
 
 impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
@@ -258,39 +644,602 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             cache: NodeCache::new(allocator),
             backrefs: BackrefContext::default(),
             flags,
+            annotation: None,
+            is_instrumentation: false,
+            scratch: Vec::new(),
+            #[cfg(feature = "stats")]
+            stats: StatsContext::default(),
         }
     }
 
-    pub(crate) fn parse_into(mut self, result: &mut String) -> Result<()> {
-        // in case of error, we should give the allocated buffer back to the user
-        macro_rules! safe_restore_buffer {
-            ($($buffer:tt)+) => {
-                let mut buffer = $($buffer)+;
+    pub(crate) fn parse_into(self, result: &mut String) -> Result<()> {
+        let mut buffer = mem::take(result).into_bytes();
+        let output = self.parse_into_bytes(&mut buffer);
+        match String::from_utf8(buffer) {
+            Ok(ok) => {
+                *result = ok;
+                output
+            }
+            Err(err) => {
+                // in case of error, we should give the allocated buffer back to the user
+                let mut buffer = err.into_bytes();
                 buffer.clear();
                 // SAFETY: buffer is an empty string at this point
                 *result = unsafe { String::from_utf8_unchecked(buffer) };
-            };
+                Err(Error::Utf8Error)
+            }
         }
+    }
 
-        let ast = self.do_parse()?.resolve(&self.cache);
-        let mut ob = BufWriter::new(mem::take(result).into_bytes());
-        if let Err(err) = ast.output(&self.cache, &mut ob, self.flags) {
-            safe_restore_buffer!(ob.into_bytes());
-            Err(err)
-        } else {
-            match String::from_utf8(ob.into_bytes()) {
-                Ok(ok) => {
-                    *result = ok;
-                    Ok(())
+    pub(crate) fn parse_into_bytes(mut self, result: &mut Vec<u8>) -> Result<()> {
+        let ast = self.parse()?;
+        let mut ob = BufWriter::new(mem::take(result));
+        let output = self.output(ast, &mut ob);
+        *result = ob.into_bytes();
+        if output.is_err() {
+            // in case of error, we should give the allocated buffer back to the user
+            result.clear();
+        }
+        output
+    }
+
+    pub(crate) fn parse_into_fmt<W: fmt::Write + ?Sized>(mut self, result: &mut W) -> Result<()> {
+        let ast = self.parse()?;
+        self.output(ast, &mut StreamWriter::new(FmtStream(result)))
+    }
+
+    pub(crate) fn parse_into_io<W: io::Write + ?Sized>(mut self, result: &mut W) -> Result<()> {
+        let ast = self.parse()?;
+        self.output(ast, &mut StreamWriter::new(IoStream(result)))
+    }
+
+    fn output(&self, ast: NodeHandle<ISymbolNode>, ob: &mut dyn Writer) -> Result<()> {
+        if let Some(annotation) = self.annotation {
+            write!(ob, "{annotation} ")?;
+        }
+        // only the parameters of the outermost function are broken onto separate lines
+        match ast.resolve(&self.cache) {
+            SymbolNode::FunctionSymbol(func)
+                if self.flags.show_function_pointer_params_inline() =>
+            {
+                func.output_params_on_lines(&self.cache, ob, self.flags)
+            }
+            symbol => symbol.output(&self.cache, ob, self.flags),
+        }
+    }
+
+    pub(crate) fn seed_names(&mut self, names: &[&str]) -> Result<()> {
+        for name in names {
+            let name = self.allocator.alloc_str(name);
+            self.memorize_string(name)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate(mut self) -> Result<()> {
+        self.parse().map(drop)
+    }
+
+    pub(crate) fn classify(mut self) -> Result<SymbolKind> {
+        let ast = self.parse()?;
+        Ok(self.symbol_kind(ast))
+    }
+
+    #[must_use]
+    fn rtti_kind(&self, name: NodeHandle<QualifiedName>) -> Option<RttiKind> {
+        let cache = &self.cache;
+        match name
+            .resolve(cache)
+            .get_unqualified_identifier(cache)
+            .map(|x| x.resolve(cache))
+        {
+            Some(IdentifierNode::RttiBaseClassDescriptor(_)) => Some(RttiKind::BaseClassDescriptor),
+            Some(IdentifierNode::NamedIdentifier(x)) => match x.rtti? {
+                SpecialIntrinsicKind::RttiTypeDescriptor => Some(RttiKind::TypeDescriptor),
+                SpecialIntrinsicKind::RttiBaseClassArray => Some(RttiKind::BaseClassArray),
+                SpecialIntrinsicKind::RttiClassHierarchyDescriptor => {
+                    Some(RttiKind::ClassHierarchyDescriptor)
                 }
-                Err(err) => {
-                    safe_restore_buffer!(err.into_bytes());
-                    Err(Error::Utf8Error)
+                SpecialIntrinsicKind::RttiCompleteObjLocator => {
+                    Some(RttiKind::CompleteObjectLocator)
                 }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    fn symbol_kind(&self, ast: NodeHandle<ISymbolNode>) -> SymbolKind {
+        let cache = &self.cache;
+        let unqualified_identifier = |name: NodeHandle<QualifiedName>| {
+            name.resolve(cache)
+                .get_unqualified_identifier(cache)
+                .map(|x| x.resolve(cache))
+        };
+        let is_rtti_name = |name| self.rtti_kind(name).is_some();
+        let is_tss_name = |name: NodeHandle<QualifiedName>| match unqualified_identifier(name) {
+            Some(IdentifierNode::NamedIdentifier(x)) => x.is_thread_safe_static_counter,
+            _ => false,
+        };
+
+        match ast.resolve(cache) {
+            SymbolNode::Md5Symbol(_) => SymbolKind::Md5,
+            SymbolNode::PassThroughSymbol(_) => SymbolKind::Foreign,
+            SymbolNode::SpecialTableSymbol(x) => {
+                if is_rtti_name(x.name) {
+                    SymbolKind::Rtti
+                } else {
+                    SymbolKind::SpecialTable
+                }
+            }
+            SymbolNode::LocalStaticGuardVariable(x) => match unqualified_identifier(x.name) {
+                Some(IdentifierNode::LocalStaticGuardIdentifier(x)) if x.is_thread => {
+                    SymbolKind::ThreadGuard
+                }
+                _ => SymbolKind::StaticGuard,
+            },
+            SymbolNode::EncodedStringLiteral(_) => SymbolKind::StringLiteral,
+            SymbolNode::VariableSymbol(x) => match x.name {
+                Some(VariableSymbolName::TypeDescriptor | VariableSymbolName::Type) => {
+                    SymbolKind::Rtti
+                }
+                Some(VariableSymbolName::ThrowInfo | VariableSymbolName::CatchableTypeArray) => {
+                    SymbolKind::SpecialTable
+                }
+                Some(VariableSymbolName::Qualified(name)) if is_rtti_name(name) => SymbolKind::Rtti,
+                Some(VariableSymbolName::Qualified(name)) if is_tss_name(name) => {
+                    SymbolKind::ThreadGuard
+                }
+                _ => SymbolKind::Variable,
+            },
+            // profile counters and data are emitted for a function, but aren't functions themselves
+            SymbolNode::FunctionSymbol(_) if self.is_instrumentation => SymbolKind::Variable,
+            SymbolNode::FunctionSymbol(_) => SymbolKind::Function,
+        }
+    }
+
+    pub(crate) fn symbol_info(mut self) -> Result<SymbolInfo> {
+        let handle = self.parse()?;
+        let kind = self.symbol_kind(handle);
+        let ast = handle.resolve(&self.cache);
+        let cache = &self.cache;
+        let unqualified_identifier = |name: NodeHandle<QualifiedName>| {
+            name.resolve(cache)
+                .get_unqualified_identifier(cache)
+                .map(|x| x.resolve(cache))
+        };
+        let type_descriptor_extra_bytes = match ast {
+            SymbolNode::VariableSymbol(x) => x.extra_bytes,
+            _ => None,
+        };
+        let three_way_comparison = match ast {
+            SymbolNode::FunctionSymbol(x)
+                if x.name.and_then(unqualified_identifier).is_some_and(|x| {
+                    matches!(
+                        x,
+                        IdentifierNode::IntrinsicFunctionIdentifier(
+                            IntrinsicFunctionIdentifierNode {
+                                operator: Some(IntrinsicFunctionKind::Spaceship),
+                                ..
+                            }
+                        )
+                    )
+                }) =>
+            {
+                let mut ob = BufWriter::new(Vec::new());
+                if let Some(return_type) = x.signature.resolve(cache).as_node().return_type {
+                    return_type.resolve(cache).output(
+                        cache,
+                        &mut ob,
+                        OutputFlags::NO_TAG_SPECIFIER,
+                    )?;
+                }
+                Some(match ob.as_bytes() {
+                    b"std::strong_ordering" => ComparisonCategory::StrongOrdering,
+                    b"std::weak_ordering" => ComparisonCategory::WeakOrdering,
+                    b"std::partial_ordering" => ComparisonCategory::PartialOrdering,
+                    _ => ComparisonCategory::Other,
+                })
+            }
+            _ => None,
+        };
+        let (structor_kind, structor_class) = match ast.get_name().map(|x| x.resolve(cache)) {
+            Some(name) => match name
+                .get_unqualified_identifier(cache)
+                .map(|x| x.resolve(cache))
+            {
+                Some(IdentifierNode::StructorIdentifier(x)) => {
+                    let kind = if x.is_destructor {
+                        StructorKind::Destructor
+                    } else {
+                        StructorKind::Constructor
+                    };
+                    let components = name.components.resolve(cache);
+                    let scope = NodeArrayNode {
+                        nodes: components.nodes.split_last().map_or(&[], |(_, x)| x),
+                    };
+                    let mut ob = BufWriter::new(Vec::new());
+                    scope.do_output(cache, &mut ob, OutputFlags::default(), "::")?;
+                    (Some(kind), Some(String::from_utf8(ob.into_bytes())?))
+                }
+                _ => (None, None),
+            },
+            None => (None, None),
+        };
+        let rtti = match ast {
+            SymbolNode::SpecialTableSymbol(x) => self.rtti_kind(x.name),
+            SymbolNode::VariableSymbol(VariableSymbolNode {
+                name: Some(VariableSymbolName::Qualified(name)),
+                ..
+            }) => self.rtti_kind(*name),
+            _ => None,
+        };
+        let variable_name = match ast {
+            SymbolNode::VariableSymbol(x) => x.name.map(|x| match x {
+                VariableSymbolName::Qualified(_) => VariableNameKind::Qualified,
+                VariableSymbolName::TypeDescriptor => VariableNameKind::TypeDescriptor,
+                VariableSymbolName::Type => VariableNameKind::Type,
+                VariableSymbolName::ThrowInfo => VariableNameKind::ThrowInfo,
+                VariableSymbolName::CatchableTypeArray => VariableNameKind::CatchableTypeArray,
+            }),
+            _ => None,
+        };
+        let access = match ast {
+            SymbolNode::FunctionSymbol(x) => {
+                let function_class = x.signature.resolve(cache).as_node().function_class;
+                if function_class.is_public() {
+                    Access::Public
+                } else if function_class.is_protected() {
+                    Access::Protected
+                } else if function_class.is_private() {
+                    Access::Private
+                } else {
+                    Access::None
+                }
+            }
+            SymbolNode::VariableSymbol(x) => match x.sc {
+                Some(StorageClass::PublicStatic) => Access::Public,
+                Some(StorageClass::ProtectedStatic) => Access::Protected,
+                Some(StorageClass::PrivateStatic) => Access::Private,
+                _ => Access::None,
+            },
+            _ => Access::None,
+        };
+        let function_attributes = match ast {
+            SymbolNode::FunctionSymbol(x) => {
+                let function_class = x.signature.resolve(cache).as_node().function_class;
+                Some(FunctionAttributes {
+                    is_far: function_class.is_far(),
+                    is_static: function_class.is_static(),
+                    is_virtual: function_class.is_virtual(),
+                    is_extern_c: function_class.is_extern_c(),
+                    no_parameter_list: function_class.no_parameter_list(),
+                    has_thunk: function_class.has_virtual_this_adjust()
+                        || function_class.has_virtual_this_adjust_ex()
+                        || function_class.has_static_this_adjust(),
+                })
+            }
+            _ => None,
+        };
+        Ok(SymbolInfo {
+            kind,
+            type_descriptor_extra_bytes,
+            three_way_comparison,
+            structor_kind,
+            structor_class,
+            variable_name,
+            access,
+            function_attributes,
+            rtti,
+        })
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn parse_with_stats(mut self) -> Result<(String, DemangleStats)> {
+        let ast = self.parse()?;
+        let mut ob = BufWriter::new(Vec::new());
+        self.output(ast, &mut ob)?;
+        let result = String::from_utf8(ob.into_bytes())?;
+        let stats = DemangleStats {
+            node_count: self.cache.len(),
+            max_depth: self.stats.max_depth,
+            max_name_backrefs: self.stats.max_name_backrefs,
+            max_param_backrefs: self.stats.max_param_backrefs,
+            output_len: result.len(),
+        };
+        Ok((result, stats))
+    }
+
+    #[cfg(feature = "stats")]
+    fn record_backrefs(&mut self) {
+        let stats = &mut self.stats;
+        stats.max_name_backrefs = stats.max_name_backrefs.max(self.backrefs.names.len());
+        stats.max_param_backrefs = stats
+            .max_param_backrefs
+            .max(self.backrefs.function_params.len());
+    }
+
+    #[cfg(feature = "debug")]
+    pub(crate) fn dump_backrefs(mut self) -> Result<Backrefs> {
+        self.parse()?;
+        let cache = &self.cache;
+        let render = |node: &dyn WriteableNode| -> Result<String> {
+            let mut ob = BufWriter::new(Vec::new());
+            node.output(cache, &mut ob, self.flags)?;
+            Ok(String::from_utf8(ob.into_bytes())?)
+        };
+        Ok(Backrefs {
+            names: self
+                .backrefs
+                .names
+                .iter()
+                .map(|x| render(x.resolve(cache)))
+                .collect::<Result<_>>()?,
+            function_params: self
+                .backrefs
+                .function_params
+                .iter()
+                .map(|x| render(&x.resolve(cache)))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub(crate) fn namespace_path(mut self) -> Result<Vec<String>> {
+        let ast = self.parse()?.resolve(&self.cache);
+        let cache = &self.cache;
+        let Some(name) = ast.get_name() else {
+            return Ok(Vec::new());
+        };
+
+        let components = name.resolve(cache).components.resolve(cache);
+        let scopes = components.nodes.split_last().map_or(&[][..], |(_, x)| x);
+        scopes
+            .iter()
+            .map(|node| {
+                let mut ob = BufWriter::new(Vec::new());
+                node.resolve(cache).output(cache, &mut ob, self.flags)?;
+                Ok(String::from_utf8(ob.into_bytes())?)
+            })
+            .collect()
+    }
+
+    pub(crate) fn leaf_name(mut self) -> Result<String> {
+        let ast = self.parse()?.resolve(&self.cache);
+        let cache = &self.cache;
+        let mut ob = BufWriter::new(Vec::new());
+        if let Some(identifier) = ast
+            .get_name()
+            .and_then(|x| x.resolve(cache).get_unqualified_identifier(cache))
+        {
+            identifier
+                .resolve(cache)
+                .output(cache, &mut ob, self.flags)?;
+        }
+        Ok(String::from_utf8(ob.into_bytes())?)
+    }
+
+    pub(crate) fn template_arguments(mut self) -> Result<Option<Vec<String>>> {
+        let ast = self.parse()?.resolve(&self.cache);
+        let cache = &self.cache;
+        let template_params = ast
+            .get_name()
+            .and_then(|x| x.resolve(cache).get_unqualified_identifier(cache))
+            .and_then(|x| match x.resolve(cache) {
+                // structors are rendered with the template arguments of their class
+                IdentifierNode::StructorIdentifier(x) if x.template_params.is_none() => x
+                    .class
+                    .and_then(|x| *x.resolve(cache).get_template_params()),
+                x => *x.get_template_params(),
+            });
+        let Some(template_params) = template_params else {
+            return Ok(None);
+        };
+
+        template_params
+            .resolve(cache)
+            .nodes
+            .iter()
+            .map(|node| {
+                let mut ob = BufWriter::new(Vec::new());
+                node.resolve(cache).output(cache, &mut ob, self.flags)?;
+                Ok(String::from_utf8(ob.into_bytes())?)
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+
+    pub(crate) fn overload_key(mut self) -> Result<Option<String>> {
+        let ast = self.parse()?.resolve(&self.cache);
+        let cache = &self.cache;
+        let SymbolNode::FunctionSymbol(func) = ast else {
+            return Ok(None);
+        };
+
+        let mut ob = BufWriter::new(Vec::new());
+        if let Some(name) = func.name {
+            name.resolve(cache).output(cache, &mut ob, self.flags)?;
+        }
+        let signature = func.signature.resolve(cache);
+        let signature = signature.as_node();
+        let count = signature.params.map_or(0, |x| x.resolve(cache).nodes.len());
+        match (count, signature.is_variadic) {
+            (0, true) => write!(ob, "(...)")?,
+            (_, true) => write!(ob, "({count}, ...)")?,
+            (_, false) => write!(ob, "({count})")?,
+        }
+        Ok(Some(String::from_utf8(ob.into_bytes())?))
+    }
+
+    fn parse(&mut self) -> Result<NodeHandle<ISymbolNode>> {
+        if self.flags.llvm_compat() {
+            return self.do_parse();
+        }
+        if let Some(label) = self.foreign_symbol_label() {
+            self.annotation = Some(label);
+            return self.demangle_pass_through_name().map(Into::into);
+        }
+        if self.mangled_name.try_consume_str("__imp_load_").is_some() {
+            // Delay-loaded imports are called through a thunk named after the import.
+            self.annotation = Some("[delay import]");
+            if self.mangled_name.as_str().starts_with(|x| x != '?') {
+                // e.g. an extern "C" function
+                return self.demangle_pass_through_name().map(Into::into);
             }
+            return self.do_parse();
+        }
+        self.annotation = self.demangle_symbol_annotation();
+        if let Some(tn) = self.try_demangle_type_name() {
+            return Ok(tn.into());
+        }
+        self.do_parse()
+    }
+
+    fn try_demangle_type_name(&mut self) -> Option<NodeHandle<VariableSymbol>> {
+        // Some tools strip the leading '.' from typeinfo names, leaving just the
+        // mangled type, e.g. ?AVfoo@@. These are indistinguishable by prefix from
+        // a symbol whose name begins with 'A', so we only accept them if the whole
+        // string parses as a type, and start over as a symbol otherwise.
+        let is_tag_type = self
+            .mangled_name
+            .as_str()
+            .strip_prefix("?A")
+            .is_some_and(|x| x.starts_with(['T', 'U', 'V', 'W']));
+        if !is_tag_type {
+            return None;
+        }
+
+        let mangled_name = self.mangled_name;
+        let backrefs = self.backrefs.clone();
+        let r#type = self
+            .demangle_type(QualifierMangleMode::Result)
+            .ok()
+            .filter(|_| self.mangled_name.is_empty());
+        let tn = r#type.and_then(|r#type| {
+            self.cache
+                .intern(VariableSymbolNode {
+                    name: Some(VariableSymbolName::Type),
+                    sc: None,
+                    r#type: Some(r#type),
+                    extra_bytes: None,
+                })
+                .ok()
+        });
+
+        if tn.is_none() {
+            self.mangled_name = mangled_name;
+            self.backrefs = backrefs;
+        }
+        tn
+    }
+
+    #[must_use]
+    fn foreign_symbol_label(&self) -> Option<&'static str> {
+        // Mixed codebases may link symbols which were never mangled by MSVC.
+        // We can't demangle them, but we can still say what they are.
+        const LABELS: [(&str, &str); 8] = [
+            ("+[", "[objc]"),
+            ("-[", "[objc]"),
+            ("_OBJC_", "[objc]"),
+            ("__delayLoadHelper", "[delay import]"),
+            ("__tailMerge_", "[delay import]"),
+            ("__DELAY_IMPORT_DESCRIPTOR_", "[delay import]"),
+            ("_Init_thread_", "[thread-safe static]"),
+            ("__Init_thread_", "[thread-safe static]"),
+        ];
+        // Runtime helpers are matched by their whole name, since their prefixes are
+        // common enough that user symbols could share them.
+        const RUNTIME_HELPERS: [&str; 36] = [
+            // control flow guard
+            "__guard_check_icall_fptr",
+            "__guard_dispatch_icall_fptr",
+            "__guard_xfg_check_icall_fptr",
+            "__guard_xfg_dispatch_icall_fptr",
+            "__guard_xfg_table_dispatch_icall_fptr",
+            "__guard_fids_table",
+            "__guard_fids_count",
+            "__guard_flags",
+            "__guard_iat_table",
+            "__guard_iat_count",
+            "__guard_longjmp_table",
+            "__guard_longjmp_count",
+            "_guard_check_icall",
+            "_guard_check_icall_nop",
+            "_guard_dispatch_icall",
+            "_guard_dispatch_icall_nop",
+            "_guard_xfg_dispatch_icall_nop",
+            // EH continuation metadata
+            "__guard_eh_cont_table",
+            "__guard_eh_cont_count",
+            // stack probes
+            "__chkstk",
+            "_chkstk",
+            "_alloca_probe",
+            "_alloca_probe_8",
+            "_alloca_probe_16",
+            // buffer security checks
+            "__security_check_cookie",
+            "__security_cookie",
+            "__security_cookie_complement",
+            "__security_init_cookie",
+            "__GSHandlerCheck",
+            "__GSHandlerCheck_EH",
+            "__GSHandlerCheck_EH4",
+            "__GSHandlerCheck_SEH",
+            // exception handling
+            "__CxxFrameHandler",
+            "__CxxFrameHandler2",
+            "__CxxFrameHandler3",
+            "__CxxFrameHandler4",
+        ];
+
+        if RUNTIME_HELPERS.contains(&self.mangled_name.as_str()) {
+            return Some("[runtime helper]");
+        }
+        LABELS
+            .iter()
+            .find(|&&(prefix, _)| self.mangled_name.starts_with(prefix))
+            .map(|&(_, label)| label)
+            .or_else(|| self.is_rust_symbol().then_some("[rust]"))
+    }
+
+    #[must_use]
+    fn is_rust_symbol(&self) -> bool {
+        // Plain C symbols may also begin with these prefixes, so we check a
+        // little more of the grammar before routing them elsewhere.
+        if self.mangled_name.starts_with("_R") {
+            rust_v0::is_symbol(self.mangled_name.as_str())
+        } else if let Some(rest) = self.mangled_name.as_str().strip_prefix("_ZN") {
+            // legacy: _ZN <path> 17h <16 hex digits> E
+            rest.starts_with(|c: char| c.is_ascii_digit())
+                && rest
+                    .strip_suffix('E')
+                    .and_then(|x| x.get(x.len().wrapping_sub(19)..))
+                    .and_then(|x| x.strip_prefix("17h"))
+                    .is_some_and(|x| x.bytes().all(|x| x.is_ascii_hexdigit()))
+        } else {
+            false
         }
     }
 
+    fn demangle_symbol_annotation(&mut self) -> Option<&'static str> {
+        // Profile-guided optimization and coverage instrumentation emits data for
+        // each function under the function's mangled name plus a prefix.
+        const ANNOTATIONS: [(&str, &str); 3] = [
+            ("__profc_", "[profile counter]"),
+            ("__profd_", "[profile data]"),
+            ("__profvp_", "[profile value]"),
+        ];
+
+        let annotation = ANNOTATIONS.iter().find_map(|&(prefix, annotation)| {
+            self.mangled_name
+                .try_consume_str(prefix)
+                .map(|_| annotation)
+        });
+        self.is_instrumentation = annotation.is_some();
+        annotation
+    }
+
     fn do_parse(&mut self) -> Result<NodeHandle<ISymbolNode>> {
         // Typeinfo names are strings stored in RTTI data. They're not symbol names.
         // It's still useful to demangle them. They're the only demangled entity
@@ -299,17 +1248,32 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             self.demangle_typeinfo_name().map(Into::into)
         } else if self.mangled_name.starts_with("??@") {
             self.demangle_md5_name().map(Into::into)
+        } else if self.flags.llvm_compat() {
+            self.demangle_mangled_name()
+        } else if self.mangled_name.starts_with("_TI") {
+            self.demangle_throw_info_name().map(Into::into)
+        } else if self.mangled_name.starts_with("_CTA") {
+            self.demangle_catchable_type_array_name().map(Into::into)
+        } else if self.mangled_name.starts_with("__real@")
+            || self.mangled_name.starts_with("__xmm@")
+            || self.mangled_name.starts_with("__ymm@")
+        {
+            self.demangle_constant_pool_name().map(Into::into)
         } else {
-            self.mangled_name
-                .try_consume_char('?')
-                .ok_or(Error::Io(io::ErrorKind::UnexpectedEof.into()))?;
-            // ?$ is a template instantiation, but all other names that start with ? are
-            // operators / special names.
-            if let Some(si) = self.demangle_special_intrinsic()? {
-                Ok(si)
-            } else {
-                self.demangle_declarator()
-            }
+            self.demangle_mangled_name()
+        }
+    }
+
+    fn demangle_mangled_name(&mut self) -> Result<NodeHandle<ISymbolNode>> {
+        self.mangled_name
+            .try_consume_char('?')
+            .ok_or(Error::Io(io::ErrorKind::UnexpectedEof.into()))?;
+        // ?$ is a template instantiation, but all other names that start with ? are
+        // operators / special names.
+        if let Some(si) = self.demangle_special_intrinsic()? {
+            Ok(si)
+        } else {
+            self.demangle_declarator()
         }
     }
 
@@ -326,6 +1290,15 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         if matches!(c, '0' | '1' | '2' | '3' | '4') {
             let sc = self.demangle_variable_storage_class()?;
             let result = self.demangle_variable_encoding(sc)?;
+            if matches!(sc, StorageClass::FunctionLocalStatic) {
+                if let Some(IdentifierNode::NamedIdentifier(x)) = name
+                    .resolve(&self.cache)
+                    .get_unqualified_identifier(&self.cache)
+                    .map(|x| x.resolve_mut(&mut self.cache))
+                {
+                    x.is_thread_safe_static_counter = x.thread_safe_static_index().is_some();
+                }
+            }
             return Ok(result.into());
         }
         let fsn = self.demangle_function_encoding()?;
@@ -412,6 +1385,85 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         self.cache.intern(s)
     }
 
+    fn demangle_pass_through_name(&mut self) -> Result<NodeHandle<PassThroughSymbol>> {
+        // The whole symbol is the name, there's nothing to demangle.
+        let rest = self.mangled_name.as_str();
+        _ = self.mangled_name.try_consume_n_bytes(rest.len());
+
+        let name = QualifiedNameNode::synthesize_from_name(self.allocator, &mut self.cache, rest)?;
+        let s = PassThroughSymbolNode {
+            name: self.cache.intern(name)?,
+        };
+
+        self.cache.intern(s)
+    }
+
+    fn demangle_constant_pool_name(&mut self) -> Result<NodeHandle<VariableSymbol>> {
+        // Floating point and vector constants are pooled into read-only data, and
+        // are named after their bit pattern, e.g. __real@3ff0000000000000 is 1.0.
+        let (prefix, payload) = self
+            .mangled_name
+            .as_str()
+            .split_once('@')
+            .ok_or(Error::InvalidConstantPoolName)?;
+        if payload.is_empty() || !payload.bytes().all(|x| x.is_ascii_hexdigit()) {
+            return Err(Error::InvalidConstantPoolName);
+        }
+
+        let mut ob = {
+            let ob = alloc::new_vec(self.allocator);
+            BufWriter::new(ob)
+        };
+        let r#type: NodeHandle<ITypeNode> = match (prefix, payload.len()) {
+            ("__real", 8) => {
+                let bits =
+                    u32::from_str_radix(payload, 16).map_err(|_| Error::InvalidConstantPoolName)?;
+                write!(ob, "{:?}", f32::from_bits(bits))?;
+                self.cache
+                    .intern(PrimitiveTypeNode::new(PrimitiveKind::Float))?
+                    .into()
+            }
+            ("__real", 16) => {
+                let bits =
+                    u64::from_str_radix(payload, 16).map_err(|_| Error::InvalidConstantPoolName)?;
+                write!(ob, "{:?}", f64::from_bits(bits))?;
+                self.cache
+                    .intern(PrimitiveTypeNode::new(PrimitiveKind::Double))?
+                    .into()
+            }
+            ("__xmm", 32) | ("__ymm", 64) => {
+                write!(ob, "0x{payload}")?;
+                let name = if prefix == "__xmm" {
+                    "__m128"
+                } else {
+                    "__m256"
+                };
+                let identifier = self.cache.intern(NamedIdentifierNode {
+                    name,
+                    ..Default::default()
+                })?;
+                self.cache
+                    .intern(CustomTypeNode {
+                        quals: Qualifiers::Q_None,
+                        identifier: identifier.into(),
+                    })?
+                    .into()
+            }
+            _ => return Err(Error::InvalidConstantPoolName),
+        };
+
+        self.mangled_name
+            .try_consume_n_bytes(self.mangled_name.len_bytes())
+            .ok_or(Error::InvalidConstantPoolName)?;
+        let node = VariableSymbolNode::synthesize(
+            self.allocator,
+            &mut self.cache,
+            r#type,
+            ob.try_into()?,
+        )?;
+        self.cache.intern(node)
+    }
+
     fn demangle_typeinfo_name(&mut self) -> Result<NodeHandle<VariableSymbol>> {
         self.mangled_name
             .try_consume_char('.')
@@ -424,9 +1476,83 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             name: Some(VariableSymbolName::TypeDescriptor),
             sc: None,
             r#type,
+            extra_bytes: None,
+        })
+    }
+
+    // Exception handling data is named after the type of the thrown object.
+    // <throw-info> ::= _TI [C] [V] [U] <number of catchable types> <type>
+    fn demangle_throw_info_name(&mut self) -> Result<NodeHandle<VariableSymbol>> {
+        self.mangled_name
+            .try_consume_str("_TI")
+            .ok_or(Error::InvalidThrowInfo)?;
+        let mut quals = Qualifiers::Q_None;
+        if self.mangled_name.try_consume_char('C').is_some() {
+            quals |= Qualifiers::Q_Const;
+        }
+        if self.mangled_name.try_consume_char('V').is_some() {
+            quals |= Qualifiers::Q_Volatile;
+        }
+        if self.mangled_name.try_consume_char('U').is_some() {
+            quals |= Qualifiers::Q_Unaligned;
+        }
+
+        let r#type = self.demangle_catchable_types(|| Error::InvalidThrowInfo)?;
+        // The qualifiers apply to the pointee, if the thrown object is a pointer.
+        if let Some(ptn) = r#type.downcast::<PointerType>(&self.cache) {
+            let pointee = ptn.resolve(&self.cache).pointee;
+            pointee.resolve_mut(&mut self.cache).append_quals(quals);
+        } else {
+            r#type.resolve_mut(&mut self.cache).append_quals(quals);
+        }
+
+        self.cache.intern(VariableSymbolNode {
+            name: Some(VariableSymbolName::ThrowInfo),
+            sc: None,
+            r#type: Some(r#type),
+            extra_bytes: None,
         })
     }
 
+    // <catchable-type-array> ::= _CTA <number of catchable types> <type>
+    fn demangle_catchable_type_array_name(&mut self) -> Result<NodeHandle<VariableSymbol>> {
+        self.mangled_name
+            .try_consume_str("_CTA")
+            .ok_or(Error::InvalidCatchableTypeArray)?;
+        let r#type = self.demangle_catchable_types(|| Error::InvalidCatchableTypeArray)?;
+        self.cache.intern(VariableSymbolNode {
+            name: Some(VariableSymbolName::CatchableTypeArray),
+            sc: None,
+            r#type: Some(r#type),
+            extra_bytes: None,
+        })
+    }
+
+    fn demangle_catchable_types(
+        &mut self,
+        error: impl Fn() -> Error,
+    ) -> Result<NodeHandle<ITypeNode>> {
+        // The number of catchable types is a plain decimal number, which we don't render.
+        let digits = self
+            .mangled_name
+            .as_str()
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if digits == 0 {
+            return Err(error());
+        }
+        self.mangled_name
+            .try_consume_n_bytes(digits)
+            .ok_or_else(&error)?;
+
+        let r#type = self.demangle_type(QualifierMangleMode::Result)?;
+        if !self.mangled_name.is_empty() {
+            return Err(error());
+        }
+        Ok(r#type)
+    }
+
     // <type-encoding> ::= <storage-class> <variable-type>
     // <storage-class> ::= 0  # private static member
     //                 ::= 1  # protected static member
@@ -458,13 +1584,18 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             pointee.append_quals(extra_child_quals);
         } else {
             let quals = self.demangle_qualifiers()?.0;
-            r#type.resolve_mut(&mut self.cache).set_quals(quals);
+            // A function type's qualifiers apply to its implicit this parameter,
+            // and must not be replaced by the variable's.
+            if r#type.downcast::<ISignatureNode>(&self.cache).is_none() {
+                r#type.resolve_mut(&mut self.cache).set_quals(quals);
+            }
         }
 
         let vsn = VariableSymbolNode {
             name: None,
             sc: Some(sc),
             r#type: Some(r#type),
+            extra_bytes: None,
         };
         self.cache.intern(vsn)
     }
@@ -544,6 +1675,20 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
     // <variable-type> ::= <type> <cvr-qualifiers>
     //                 ::= <type> <pointee-cvr-qualifiers> # pointers, references
     fn demangle_type(&mut self, qmm: QualifierMangleMode) -> Result<NodeHandle<ITypeNode>> {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.depth += 1;
+            self.stats.max_depth = self.stats.max_depth.max(self.stats.depth);
+        }
+        let result = self.do_demangle_type(qmm);
+        #[cfg(feature = "stats")]
+        {
+            self.stats.depth -= 1;
+        }
+        result
+    }
+
+    fn do_demangle_type(&mut self, qmm: QualifierMangleMode) -> Result<NodeHandle<ITypeNode>> {
         let quals = match qmm {
             QualifierMangleMode::Mangle => self.demangle_qualifiers()?.0,
             QualifierMangleMode::Result => {
@@ -851,6 +1996,8 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                         1 => (), // Single-letter types are ignored for backreferences because memorizing them doesn't save anything.
                         _ => {
                             _ = self.backrefs.function_params.try_push(tn);
+                            #[cfg(feature = "stats")]
+                            self.record_backrefs();
                         }
                     };
                 }
@@ -884,7 +2031,9 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                 || self.mangled_name.try_consume_str("$$$V").is_some()
                 || self.mangled_name.try_consume_str("$$Z").is_some()
             {
-                // parameter pack separator
+                // Empty packs ($S, $$V, $$$V) contribute no arguments, and $$Z only
+                // marks where one pack expansion ends and the next argument begins.
+                // Either way, the surrounding arguments are still joined by commas.
                 continue;
             }
             if self.mangled_name.is_empty() {
@@ -1091,35 +2240,63 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
 
     // First 10 strings can be referenced by special BackReferences ?0, ?1, ..., ?9.
     // Memorize it.
-    fn memorize_string(&mut self, s: &'alloc str) -> Result<()> {
-        if !self.backrefs.names.is_full()
+    #[must_use]
+    fn can_memorize_string(&self, s: &str) -> bool {
+        !self.backrefs.names.is_full()
             && self
                 .backrefs
                 .names
                 .iter()
                 .all(|x| x.resolve(&self.cache).name != s)
-        {
-            let name = self.cache.intern(NamedIdentifierNode {
-                name: s,
-                ..Default::default()
-            })?;
+    }
+
+    fn memorize_string(&mut self, s: &'alloc str) -> Result<()> {
+        self.memorize_name(NamedIdentifierNode {
+            name: s,
+            ..Default::default()
+        })
+    }
+
+    fn memorize_name(&mut self, identifier: NamedIdentifierNode<'alloc>) -> Result<()> {
+        if self.can_memorize_string(identifier.name) {
+            let name = self.cache.intern(identifier)?;
             // SAFETY: we just verified the array is not full
             unsafe { self.backrefs.names.push_unchecked(name) };
+            #[cfg(feature = "stats")]
+            self.record_backrefs();
         }
         Ok(())
     }
 
     fn memorize_identifier(&mut self, identifier: NodeHandle<IIdentifierNode>) -> Result<()> {
         // Render this class template name into a string buffer so that we can
-        // memorize it for the purpose of back-referencing.
-        let mut ob = {
-            let ob = alloc::new_vec(self.allocator);
-            BufWriter::new(ob)
-        };
+        // memorize it for the purpose of back-referencing. Names which are
+        // already memorized never need to be copied into the arena. The name is
+        // rendered without any flags, since some of them print distinct names
+        // alike, e.g. A<int> and A<double> as A<...>. The identifier itself is
+        // what gets rendered in place of the back-reference.
+        let mut ob = self.take_scratch();
         identifier
             .resolve(&self.cache)
-            .output(&self.cache, &mut ob, self.flags)?;
-        self.memorize_string(ob.try_into()?)
+            .output(&self.cache, &mut ob, OutputFlags::empty())?;
+        let name = str::from_utf8(ob.as_bytes())?;
+        if self.can_memorize_string(name) {
+            let name = self.allocator.alloc_str(name);
+            self.memorize_name(NamedIdentifierNode {
+                name,
+                memorized_from: Some(identifier),
+                ..Default::default()
+            })?;
+        }
+        self.scratch = ob.into_bytes();
+        Ok(())
+    }
+
+    #[must_use]
+    fn take_scratch(&mut self) -> BufWriter<Vec<u8>> {
+        let mut buffer = mem::take(&mut self.scratch);
+        buffer.clear();
+        BufWriter::new(buffer)
     }
 
     // Parses a type name in the form of A@B@C@@ which represents C::B::A.
@@ -1258,13 +2435,22 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             .ok_or(Error::InvalidTemplateInstantiationName)?;
 
         let outer_context = mem::take(&mut self.backrefs);
-        let identifier = self.demangle_unqualified_symbol_name(NameBackrefBehavior::NBB_Simple)?;
-        {
-            let template_params = self.demangle_template_parameter_list()?;
-            identifier
-                .resolve_mut(&mut self.cache)
-                .set_template_params(template_params);
-        }
+        let generic = if self.flags.llvm_compat() {
+            None
+        } else {
+            self.try_demangle_generic_instantiation()
+        };
+        let is_generic = generic.is_some();
+        let (identifier, template_params) = if let Some(generic) = generic {
+            generic
+        } else {
+            let identifier =
+                self.demangle_unqualified_symbol_name(NameBackrefBehavior::NBB_Simple)?;
+            (identifier, self.demangle_template_parameter_list()?)
+        };
+        identifier
+            .resolve_mut(&mut self.cache)
+            .set_template_params(template_params, is_generic);
 
         _ = mem::replace(&mut self.backrefs, outer_context);
         if nbb.is_template() {
@@ -1284,6 +2470,38 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         Ok(identifier)
     }
 
+    // C++/CLI generics are introduced with ?$$H rather than ?$. Since a native template's
+    // name may also begin with '$H', the marker is only taken as such if it's followed by a
+    // plain name and a list of types, which is all a generic can be instantiated with.
+    // Otherwise, we rewind and let the caller demangle a native template instead.
+    fn try_demangle_generic_instantiation(
+        &mut self,
+    ) -> Option<(NodeHandle<IIdentifierNode>, NodeHandle<NodeArray>)> {
+        let mangled_name = self.mangled_name;
+        self.mangled_name.try_consume_str("$H")?;
+
+        let generic = self
+            .demangle_unqualified_symbol_name(NameBackrefBehavior::NBB_Simple)
+            .ok()
+            .filter(|x| matches!(x.resolve(&self.cache), IdentifierNode::NamedIdentifier(_)))
+            .and_then(|identifier| {
+                let template_params = self.demangle_template_parameter_list().ok()?;
+                let cache = &self.cache;
+                template_params
+                    .resolve(cache)
+                    .nodes
+                    .iter()
+                    .all(|x| x.downcast::<ITypeNode>(cache).is_some())
+                    .then_some((identifier, template_params))
+            });
+
+        if generic.is_none() {
+            self.mangled_name = mangled_name;
+            self.backrefs = BackrefContext::default();
+        }
+        generic
+    }
+
     fn translate_intrinsic_function_code(
         ch: char,
         group: FunctionIdentifierCodeGroup,
@@ -1479,7 +2697,15 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
     fn demangle_literal_operator_identifier(
         &mut self,
     ) -> Result<NodeHandle<LiteralOperatorIdentifier>> {
-        let name = self.demangle_simple_string(false)?;
+        let name = self.demangle_simple_string(true)?;
+        // the suffix of a literal operator is an identifier, which can't contain mangling syntax
+        let is_identifier = !name.starts_with(|c: char| c.is_ascii_digit())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$' || !c.is_ascii());
+        if !is_identifier {
+            return Err(Error::InvalidSimpleString);
+        }
         self.cache.intern(LiteralOperatorIdentifierNode {
             name,
             ..Default::default()
@@ -1509,22 +2735,43 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                     self.mangled_name
                         .try_consume_str("@8")
                         .ok_or(Error::InvalidSpecialIntrinsic)?;
+                    // Some producers follow the descriptor with the number of spare bytes after its name.
+                    let extra_bytes = if self.mangled_name.is_empty() {
+                        None
+                    } else {
+                        Some(self.demangle_unsigned()?)
+                    };
                     if !self.mangled_name.is_empty() {
                         return Err(Error::InvalidSpecialIntrinsic);
                     }
-                    let node = VariableSymbolNode::synthesize(
+                    let ni = self.cache.intern(NamedIdentifierNode {
+                        name: self.rtti_name("`RTTI Type Descriptor'"),
+                        rtti: Some(sik),
+                        ..Default::default()
+                    })?;
+                    let name = QualifiedNameNode::synthesize_from_id(
                         self.allocator,
                         &mut self.cache,
-                        t,
-                        "`RTTI Type Descriptor'",
+                        ni.into(),
                     )?;
-                    self.cache.intern(node)?.into()
+                    let name = self.cache.intern(name)?;
+                    self.cache
+                        .intern(VariableSymbolNode {
+                            name: Some(name.into()),
+                            r#type: Some(t),
+                            extra_bytes,
+                            ..Default::default()
+                        })?
+                        .into()
                 }
                 SpecialIntrinsicKind::RttiBaseClassArray => self
-                    .demangle_untyped_variable("`RTTI Base Class Array'")?
+                    .demangle_untyped_variable(self.rtti_name("`RTTI Base Class Array'"), sik)?
                     .into(),
                 SpecialIntrinsicKind::RttiClassHierarchyDescriptor => self
-                    .demangle_untyped_variable("`RTTI Class Hierarchy Descriptor'")?
+                    .demangle_untyped_variable(
+                        self.rtti_name("`RTTI Class Hierarchy Descriptor'"),
+                        sik,
+                    )?
                     .into(),
                 SpecialIntrinsicKind::RttiBaseClassDescriptor => {
                     self.demangle_rtti_base_class_descriptor_node()?.into()
@@ -1535,7 +2782,8 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                 SpecialIntrinsicKind::DynamicAtexitDestructor => {
                     self.demangle_init_fini_stub(true)?.into()
                 }
-                SpecialIntrinsicKind::Typeof | SpecialIntrinsicKind::UdtReturning => {
+                SpecialIntrinsicKind::UdtReturning => self.demangle_udt_returning()?,
+                SpecialIntrinsicKind::Typeof => {
                     // It's unclear which tools produces these manglings, so demangling
                     // support is not (yet?) implemented.
                     return Err(Error::InvalidSpecialIntrinsic);
@@ -1547,6 +2795,46 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         }
     }
 
+    // Older toolchains prefix the name of a function returning a user-defined type
+    // by value with ?_P, e.g. ??_P?Hklass@@QAE?AV0@ABV0@@Z
+    fn demangle_udt_returning(&mut self) -> Result<NodeHandle<ISymbolNode>> {
+        let identifier = self
+            .demangle_unqualified_symbol_name(NameBackrefBehavior::NBB_Simple)
+            .map_err(|_| Error::InvalidUdtReturning)?;
+        if matches!(
+            identifier.resolve(&self.cache),
+            IdentifierNode::StructorIdentifier(_) | IdentifierNode::ConversionOperatorIdentifier(_)
+        ) {
+            return Err(Error::InvalidUdtReturning);
+        }
+
+        let mut ob = self.take_scratch();
+        write!(ob, "`udt returning'")?;
+        identifier
+            .resolve(&self.cache)
+            .output(&self.cache, &mut ob, self.flags)?;
+        let name = self.allocator.alloc_str(str::from_utf8(ob.as_bytes())?);
+        self.scratch = ob.into_bytes();
+        let identifier = self.cache.intern(NamedIdentifierNode {
+            name,
+            ..Default::default()
+        })?;
+
+        let qn = self.demangle_name_scope_chain(identifier.into())?;
+        let symbol = self.demangle_function_encoding()?;
+        symbol.resolve_mut(&mut self.cache).name = Some(qn);
+        Ok(symbol.into())
+    }
+
+    #[must_use]
+    fn rtti_name(&self, name: &'static str) -> &'static str {
+        if self.flags.no_rtti_quote_decoration() {
+            name.trim_start_matches('`').trim_end_matches('\'')
+        } else {
+            name
+        }
+    }
+
     fn demangle_special_table_symbol_node(
         &mut self,
         k: SpecialIntrinsicKind,
@@ -1555,12 +2843,16 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             SpecialIntrinsicKind::Vftable => "`vftable'",
             SpecialIntrinsicKind::Vbtable => "`vbtable'",
             SpecialIntrinsicKind::LocalVftable => "`local vftable'",
-            SpecialIntrinsicKind::RttiCompleteObjLocator => "`RTTI Complete Object Locator'",
+            SpecialIntrinsicKind::RttiCompleteObjLocator => {
+                self.rtti_name("`RTTI Complete Object Locator'")
+            }
             _ => return Err(Error::InvalidSpecialTableSymbolNode),
         };
 
+        let rtti = matches!(k, SpecialIntrinsicKind::RttiCompleteObjLocator).then_some(k);
         let ni = self.cache.intern(NamedIdentifierNode {
             name: intrinsic_name,
+            rtti,
             ..Default::default()
         })?;
         let name = self.demangle_name_scope_chain(ni.into())?;
@@ -1570,15 +2862,28 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             .ok_or(Error::InvalidSpecialTableSymbolNode)?;
 
         let (quals, _) = self.demangle_qualifiers()?;
-        let target_name = if self.mangled_name.try_consume_char('@').is_none() {
-            Some(self.demangle_fully_qualified_type_name()?)
+        // The target is a path of base classes, e.g. {for `A's `B'}, terminated by '@'.
+        let target_names = if self.mangled_name.try_consume_char('@').is_none() {
+            let mut nodes = SmallVec::<[NodeHandle<INode>; 4]>::new();
+            loop {
+                nodes.push(self.demangle_fully_qualified_type_name()?.into());
+                if self.mangled_name.is_empty() {
+                    return Err(Error::InvalidSpecialTableSymbolNode);
+                }
+                if self.mangled_name.try_consume_char('@').is_some() {
+                    break;
+                }
+            }
+            Some(self.cache.intern(NodeArrayNode {
+                nodes: alloc::allocate_slice(self.allocator, &nodes),
+            })?)
         } else {
             None
         };
 
         self.cache.intern(SpecialTableSymbolNode {
             name,
-            target_name,
+            target_names,
             quals,
         })
     }
@@ -1615,9 +2920,11 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
     fn demangle_untyped_variable(
         &mut self,
         variable_name: &'static str,
+        rtti: SpecialIntrinsicKind,
     ) -> Result<NodeHandle<VariableSymbol>> {
         let ni = self.cache.intern(NamedIdentifierNode {
             name: variable_name,
+            rtti: Some(rtti),
             ..Default::default()
         })?;
         let name = Some(self.demangle_name_scope_chain(ni.into())?.into());
@@ -1754,18 +3061,24 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             .mangled_name
             .try_consume_n_bytes(pos)
             .ok_or(Error::InvalidAnonymousNamespaceName)?;
-        self.memorize_string(namespace_key)?;
+        let name = "`anonymous namespace'";
+        // An empty key still takes up a backref slot, but would otherwise be
+        // referenced as an empty scope, e.g. "::B".
+        self.memorize_string(if namespace_key.is_empty() {
+            name
+        } else {
+            namespace_key
+        })?;
         self.mangled_name
             .try_consume_char('@')
             .ok_or(Error::InvalidAnonymousNamespaceName)?;
         self.cache.intern(NamedIdentifierNode {
-            name: "`anonymous namespace'",
+            name,
             ..Default::default()
         })
     }
 
     fn demangle_locally_scoped_name_piece(&mut self) -> Result<NodeHandle<NamedIdentifier>> {
-        let mut identifier = NamedIdentifierNode::default();
         self.mangled_name
             .try_consume_char('?')
             .ok_or(Error::InvalidLocallyScopedNamePiece)?;
@@ -1778,19 +3091,12 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         self.mangled_name
             .try_consume_char('?')
             .ok_or(Error::InvalidLocallyScopedNamePiece)?;
-        let scope = self.do_parse()?.resolve(&self.cache);
-
-        // Render the parent symbol's name into a buffer.
-        let mut ob = {
-            let ob = alloc::new_vec(self.allocator);
-            BufWriter::new(ob)
-        };
-        write!(ob, "`")?;
-        scope.output(&self.cache, &mut ob, self.flags)?;
-        write!(ob, "'::`{number}'")?;
+        let scope = self.do_parse()?;
 
-        identifier.name = ob.try_into()?;
-        self.cache.intern(identifier)
+        self.cache.intern(NamedIdentifierNode {
+            local_scope: Some((scope, number)),
+            ..Default::default()
+        })
     }
 
     fn demangle_string_literal(&mut self) -> Result<NodeHandle<EncodedStringLiteral>> {
@@ -1816,13 +3122,21 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         }
 
         // CRC 32 (always 8 characters plus a terminator)
-        let crc_end_pos = self
-            .mangled_name
-            .find_char('@')
-            .ok_or(Error::InvalidStringLiteral)?;
-        self.mangled_name
-            .try_consume_n_bytes(crc_end_pos + 1)
-            .ok_or(Error::InvalidStringLiteral)?;
+        let expected_crc = if self.flags.validate_string_crc() {
+            let crc = self
+                .demangle_unsigned()
+                .map_err(|_| Error::InvalidStringLiteral)?;
+            Some(crc)
+        } else {
+            let crc_end_pos = self
+                .mangled_name
+                .find_char('@')
+                .ok_or(Error::InvalidStringLiteral)?;
+            self.mangled_name
+                .try_consume_n_bytes(crc_end_pos + 1)
+                .ok_or(Error::InvalidStringLiteral)?;
+            None
+        };
         if self.mangled_name.is_empty() {
             return Err(Error::InvalidStringLiteral);
         }
@@ -1831,10 +3145,11 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             let ob = alloc::new_vec(self.allocator);
             BufWriter::new(ob)
         };
-        let (char, is_truncated) = if is_wchar_t {
+        let (char, is_truncated, crc) = if is_wchar_t {
             let char = CharKind::Wchar;
             let is_truncated = string_byte_len > 64;
 
+            let mut crc = u32::MAX;
             while self.mangled_name.try_consume_char('@').is_none() {
                 if self.mangled_name.len_bytes() < 2 {
                     return Err(Error::InvalidStringLiteral);
@@ -1844,9 +3159,13 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                     Self::output_escaped_char(&mut ob, w.into())?;
                 }
                 string_byte_len = string_byte_len.saturating_sub(2);
+                // wide characters are mangled big-endian, but checksummed little-endian
+                for byte in w.to_le_bytes() {
+                    crc = Self::update_string_literal_crc(crc, byte);
+                }
             }
 
-            (char, is_truncated)
+            (char, is_truncated, crc)
         } else {
             // The max byte length is actually 32, but some compilers mangled strings
             // incorrectly, so we have to assume it can go higher.
@@ -1880,9 +3199,17 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
                 }
             }
 
-            (char, is_truncated)
+            let crc = string_bytes.iter().fold(u32::MAX, |crc, &byte| {
+                Self::update_string_literal_crc(crc, byte)
+            });
+            (char, is_truncated, crc)
         };
 
+        // The CRC covers the whole string, so we can only check it if nothing was truncated.
+        if expected_crc.is_some_and(|x| !is_truncated && x != u64::from(crc)) {
+            return Err(Error::InvalidStringLiteral);
+        }
+
         let result = EncodedStringLiteralNode {
             name: None,
             decoded_string: ob.try_into()?,
@@ -1937,6 +3264,9 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
             self.mangled_name
                 .try_consume_char('@')
                 .ok_or(Error::InvalidSimpleString)?;
+            if self.flags.strict_utf8_identifiers() && string.chars().any(char::is_control) {
+                return Err(Error::InvalidSimpleString);
+            }
             if memorize {
                 self.memorize_string(string)?;
             }
@@ -2169,6 +3499,8 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
     fn demangle_pointer_cv_qualifiers(&mut self) -> Result<(Qualifiers, PointerAffinity)> {
         if self.mangled_name.try_consume_str("$$Q").is_some() {
             Ok((Qualifiers::Q_None, PointerAffinity::RValueReference))
+        } else if self.mangled_name.try_consume_str("$$R").is_some() {
+            Ok((Qualifiers::Q_Volatile, PointerAffinity::RValueReference))
         } else {
             let f = self
                 .mangled_name
@@ -2300,6 +3632,20 @@ impl<'alloc, 'string: 'alloc> Demangler<'alloc, 'string> {
         }
     }
 
+    // MSVC checksums string literals, including their null terminator, using a CRC-32 without the final inversion.
+    #[must_use]
+    fn update_string_literal_crc(crc: u32, byte: u8) -> u32 {
+        let mut crc = crc ^ u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            };
+        }
+        crc
+    }
+
     fn decode_multi_byte_char(
         string_bytes: &[u8],
         char_index: usize,
@@ -16,10 +16,13 @@
 #![warn(clippy::pedantic)]
 #![deny(clippy::undocumented_unsafe_blocks)]
 #![allow(
+    clippy::collapsible_match,
+    clippy::manual_is_multiple_of, // usize::is_multiple_of requires rust 1.87
     clippy::missing_errors_doc,
     clippy::similar_names,
     clippy::struct_field_names,
-    clippy::too_many_lines
+    clippy::too_many_lines,
+    clippy::unnecessary_semicolon
 )]
 
 mod alloc;
@@ -28,23 +31,66 @@ mod demangler;
 mod extensions;
 mod mangled_string;
 mod nodes;
+mod sink;
 
 #[cfg(test)]
+#[allow(
+    clippy::assertions_on_constants,
+    clippy::needless_borrow,
+    clippy::semicolon_if_nothing_returned,
+    clippy::useless_conversion
+)]
 mod tests;
 
 use crate::demangler::Demangler;
+pub use crate::sink::DemangleSink;
 use bumpalo::Bump;
 use std::{
+    fmt,
     io,
-    str::Utf8Error,
+    str::{
+        self,
+        Utf8Error,
+    },
     string::FromUtf8Error,
 };
 
 type OutputFlags = Flags;
 
-trait Writer: io::Write {
+trait Writer {
+    fn write_str(&mut self, s: &str) -> Result<()>;
     fn last_char(&self) -> Option<char>;
     fn len_bytes(&self) -> usize;
+
+    // Enables use of the `write!` macro, but propagates our own errors instead of `fmt::Error`.
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> Result<()> {
+        struct Adapter<'a, W: ?Sized> {
+            inner: &'a mut W,
+            result: Result<()>,
+        }
+
+        impl<W: Writer + ?Sized> fmt::Write for Adapter<'_, W> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.inner.write_str(s).map_err(|err| {
+                    self.result = Err(err);
+                    fmt::Error
+                })
+            }
+        }
+
+        if let Some(s) = args.as_str() {
+            return self.write_str(s);
+        }
+
+        let mut adapter = Adapter {
+            inner: self,
+            result: Ok(()),
+        };
+        match fmt::write(&mut adapter, args) {
+            Ok(()) => Ok(()),
+            Err(err) => adapter.result.and(Err(err.into())),
+        }
+    }
 }
 
 #[non_exhaustive]
@@ -62,12 +108,18 @@ pub enum Error {
     #[error("failed to demangle calling convention")]
     InvalidCallingConvention,
 
+    #[error("failed to demangle catchable type array")]
+    InvalidCatchableTypeArray,
+
     #[error("failed to demangle char literal")]
     InvalidCharLiteral,
 
     #[error("failed to demangle class type")]
     InvalidClassType,
 
+    #[error("failed to demangle constant pool name")]
+    InvalidConstantPoolName,
+
     #[error("failed to demangle custom type")]
     InvalidCustomType,
 
@@ -158,6 +210,9 @@ pub enum Error {
     #[error("failed to demangle template parameter list")]
     InvalidTemplateParameterList,
 
+    #[error("failed to demangle throw info")]
+    InvalidThrowInfo,
+
     #[error("failed to demangle throw specification")]
     InvalidThrowSpecification,
 
@@ -167,6 +222,9 @@ pub enum Error {
     #[error("failed to demangle typinfo name")]
     InvalidTypeinfoName,
 
+    #[error("failed to demangle udt returning name")]
+    InvalidUdtReturning,
+
     #[error("failed to demangle unsigned number")]
     InvalidUnsigned,
 
@@ -182,6 +240,9 @@ pub enum Error {
     #[error(transparent)]
     Io(#[from] io::Error),
 
+    #[error(transparent)]
+    Fmt(#[from] fmt::Error),
+
     #[error("string demangled to an invalid utf-8 sequence")]
     Utf8Error,
 
@@ -203,10 +264,257 @@ impl From<FromUtf8Error> for Error {
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The number of names which can be back-referenced in a mangled symbol, using the digits `0` to `9`.
+/// Any names past this limit are not memorized, and must be spelled out in full.
+pub const MAX_NAME_BACKREFS: usize = 10;
+
+/// The number of function parameter types which can be back-referenced in a mangled symbol, using the digits `0` to `9`.
+/// Only parameter types which take more than one character to mangle are memorized.
+pub const MAX_PARAM_BACKREFS: usize = 10;
+
+/// The contents of the back-reference tables after demangling a symbol. See [`dump_backrefs`] for more info.
+#[cfg(feature = "debug")]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Backrefs {
+    /// The memorized names, in the order they can be back-referenced.
+    pub names: Vec<String>,
+
+    /// The memorized function parameter types, in the order they can be back-referenced.
+    pub function_params: Vec<String>,
+}
+
+/// Statistics about the work done while demangling a symbol. See [`demangle_with_stats`] for more info.
+#[cfg(feature = "stats")]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DemangleStats {
+    /// The number of nodes parsed from the symbol.
+    pub node_count: usize,
+
+    /// The deepest nesting of types reached while parsing, e.g. through pointers, function parameters and template
+    /// arguments.
+    pub max_depth: usize,
+
+    /// The most names memorized for back-referencing at once. See [`MAX_NAME_BACKREFS`] for more info.
+    pub max_name_backrefs: usize,
+
+    /// The most function parameter types memorized for back-referencing at once. See [`MAX_PARAM_BACKREFS`] for more
+    /// info.
+    pub max_param_backrefs: usize,
+
+    /// The length of the demangled name, in bytes.
+    pub output_len: usize,
+}
+
+/// The kind of entity a mangled symbol refers to. See [`classify`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SymbolKind {
+    /// A function, including thunks and dynamic initializers/destructors.
+    Function,
+
+    /// A variable, including the profile instrumentation data emitted for a function, e.g. `__profc_?foo@@YAXXZ`.
+    Variable,
+
+    /// A compiler generated table, such as a `vftable` or `vbtable`.
+    SpecialTable,
+
+    /// Run-time type information, such as a type descriptor or complete object locator.
+    Rtti,
+
+    /// A string literal.
+    StringLiteral,
+
+    /// A symbol whose name was too long, and was replaced with its md5 hash.
+    Md5,
+
+    /// A guard for a thread-safe local static variable.
+    ThreadGuard,
+
+    /// A guard for a local static variable.
+    StaticGuard,
+
+    /// A symbol which was not mangled by a Microsoft compiler, such as one from another language's mangling scheme,
+    /// which is passed through as-is.
+    Foreign,
+}
+
+/// Metadata about a mangled symbol, extracted without rendering it. See [`symbol_info`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SymbolInfo {
+    /// The kind of entity the symbol refers to.
+    pub kind: SymbolKind,
+
+    /// The number of spare bytes reserved after the name of an RTTI type descriptor, for producers which encode it.
+    pub type_descriptor_extra_bytes: Option<u64>,
+
+    /// The comparison category returned by the symbol, if it is an `operator<=>`.
+    pub three_way_comparison: Option<ComparisonCategory>,
+
+    /// Whether the symbol is a constructor or a destructor.
+    pub structor_kind: Option<StructorKind>,
+
+    /// The fully qualified name of the class the constructor or destructor belongs to.
+    pub structor_class: Option<String>,
+
+    /// How the name of a variable symbol is encoded.
+    pub variable_name: Option<VariableNameKind>,
+
+    /// The access specifier of a member function or static data member.
+    pub access: Access,
+
+    /// The ABI-relevant attributes of a function symbol.
+    pub function_attributes: Option<FunctionAttributes>,
+
+    /// Which run-time type information structure a `??_R` symbol refers to. MSVC doesn't mangle the attributes of
+    /// these structures (e.g. whether a class hierarchy uses virtual inheritance), so only the kind is available.
+    pub rtti: Option<RttiKind>,
+}
+
+/// The kind of a run-time type information structure. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RttiKind {
+    /// A type descriptor, e.g. `??_R0?AUBase@@@8`.
+    TypeDescriptor,
+
+    /// A base class descriptor, e.g. `??_R1A@?0A@EA@Base@@8`.
+    BaseClassDescriptor,
+
+    /// A base class array, e.g. `??_R2Base@@8`.
+    BaseClassArray,
+
+    /// A class hierarchy descriptor, e.g. `??_R3Base@@8`.
+    ClassHierarchyDescriptor,
+
+    /// A complete object locator, e.g. `??_R4Base@@6B@`.
+    CompleteObjectLocator,
+}
+
+/// The attributes of a function symbol, as encoded by its function class. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct FunctionAttributes {
+    /// Whether the function is `__far`, which is only meaningful for segmented architectures.
+    pub is_far: bool,
+
+    /// Whether the function is a static member function.
+    pub is_static: bool,
+
+    /// Whether the function is a virtual member function.
+    pub is_virtual: bool,
+
+    /// Whether the function has `extern "C"` linkage.
+    pub is_extern_c: bool,
+
+    /// Whether the mangling omits the function's parameters, e.g. `?f@@9`.
+    pub no_parameter_list: bool,
+
+    /// Whether the function is a thunk which adjusts `this` before calling the real function.
+    pub has_thunk: bool,
+}
+
+/// The access specifier of a class member. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Access {
+    /// A `public:` member function or static data member.
+    Public,
+
+    /// A `protected:` member function or static data member.
+    Protected,
+
+    /// A `private:` member function or static data member.
+    Private,
+
+    /// Anything which isn't a class member, e.g. a free function or a global variable.
+    None,
+}
+
+/// The kind of a special member function. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StructorKind {
+    /// A constructor, e.g. `klass::klass`.
+    Constructor,
+
+    /// A destructor, e.g. `klass::~klass`.
+    Destructor,
+}
+
+/// How the name of a variable symbol is encoded. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VariableNameKind {
+    /// A regular qualified name, e.g. `?x@ns@@3HA`.
+    Qualified,
+
+    /// The name of an RTTI type descriptor, e.g. `.?AUBase@@`.
+    TypeDescriptor,
+
+    /// The mangled type of a type descriptor, without the descriptor itself, e.g. `?AUBase@@`.
+    Type,
+
+    /// The exception handling data for a thrown type, e.g. `_TI1?AVexception@std@@`.
+    ThrowInfo,
+
+    /// The array of types which can catch a thrown type, e.g. `_CTA1?AVexception@std@@`.
+    CatchableTypeArray,
+}
+
+/// The comparison category returned by an `operator<=>`. See [`SymbolInfo`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ComparisonCategory {
+    /// `std::strong_ordering`
+    StrongOrdering,
+
+    /// `std::weak_ordering`
+    WeakOrdering,
+
+    /// `std::partial_ordering`
+    PartialOrdering,
+
+    /// Any other return type, such as `bool` or a user defined type.
+    Other,
+}
+
+/// The compiler which most likely produced a mangled symbol. See [`producer_hint`] for more info.
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Producer {
+    /// The Microsoft C++ compiler.
+    Msvc,
+
+    /// Clang in its MSVC compatible mode, i.e. `clang-cl`.
+    ClangCl,
+
+    /// The symbol is not a Microsoft symbol, or could not be demangled.
+    Unknown,
+}
+
 bitflags::bitflags! {
     /// `Flags` control how types are printed during demangling. See each flag for more info on what exactly they do.
+    ///
+    /// Prior to 3.0.0, `Flags` was backed by a `u16`. It has since been widened to make room for new flags, which changes
+    /// the integer type accepted and returned by [`Flags::bits`], [`Flags::from_bits`], and friends. It is now backed by
+    /// a `u64`, as the flags have outgrown the low 32 bits (see [`Flags::NO_CC_ON_DATA`]).
     #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-    pub struct Flags: u16 {
+    pub struct Flags: u64 {
         /// Suppress calling conventions (`__cdecl`/`__fastcall`/`__thiscall`) from being included in the output.
         /// ```rust
         /// use undname::Flags;
@@ -328,10 +636,350 @@ bitflags::bitflags! {
         /// assert_eq!(with_flag,    "hello::world");
         /// ```
         const NAME_ONLY = 1 << 9;
+
+        /// Output `__int64`/`unsigned __int64` as `ptrdiff_t`/`size_t`. This assumes an LLP64 target, where these are the platform-width integers.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??Aklass@@QEAAH_K@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::SIZE_T_ALIASES).unwrap();
+        /// assert_eq!(without_flag, "public: int __cdecl klass::operator[](unsigned __int64)");
+        /// assert_eq!(with_flag,    "public: int __cdecl klass::operator[](size_t)");
+        /// ```
+        const SIZE_T_ALIASES = 1 << 10;
+
+        /// Suppress the parameter list of the primary declaration from being included in the output.
+        /// The qualifiers and exception specification which trail the parameter list are suppressed along with it.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?func@MyClass@@UEAAHHH@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_ARGUMENTS).unwrap();
+        /// assert_eq!(without_flag, "public: virtual int __cdecl MyClass::func(int, int)");
+        /// assert_eq!(with_flag,    "public: virtual int __cdecl MyClass::func");
+        /// ```
+        const NO_ARGUMENTS = 1 << 11;
+
+        /// Output only the name for the primary declaration, without any arguments. This mirrors `UNDNAME_NAME_ONLY | UNDNAME_NO_ARGUMENTS`.
+        /// [`NAME_ONLY`](Self::NAME_ONLY) takes precedence over [`NO_ARGUMENTS`](Self::NO_ARGUMENTS) when both are set.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??$Foo@H@?$BoolTemplate@$00@@QEAAXH@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::SIGNATURE_ONLY).unwrap();
+        /// assert_eq!(without_flag, "public: void __cdecl BoolTemplate<1>::Foo<int>(int)");
+        /// assert_eq!(with_flag,    "BoolTemplate<1>::Foo<int>");
+        /// ```
+        const SIGNATURE_ONLY = Self::NAME_ONLY.bits() | Self::NO_ARGUMENTS.bits();
+
+        /// Reject identifiers containing control characters (such as embedded nulls), instead of passing them through to the output.
+        /// This is useful when the output is used as a filename or shell argument. String literals are unaffected.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?x\n@@3HA";
+        /// let without_flag = undname::demangle(input, Flags::default());
+        /// let with_flag = undname::demangle(input, Flags::STRICT_UTF8_IDENTIFIERS);
+        /// assert_eq!(without_flag.unwrap(), "int x\n");
+        /// assert!(with_flag.is_err());
+        /// ```
+        const STRICT_UTF8_IDENTIFIERS = 1 << 12;
+
+        /// Replace compiler generated disambiguators which are not stable across builds (lambda numbers and local scope indices) with placeholders.
+        /// Anonymous namespace keys are never included in the output.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??R<lambda_1>@?0??main@@YAHXZ@QBE@XZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::BUILD_STABLE_NAMES).unwrap();
+        /// assert_eq!(without_flag, "public: __thiscall `int __cdecl main(void)'::`1'::<lambda_1>::operator()(void) const");
+        /// assert_eq!(with_flag,    "public: __thiscall `int __cdecl main(void)'::`#'::<lambda>::operator()(void) const");
+        /// ```
+        const BUILD_STABLE_NAMES = 1 << 13;
+
+        /// Suppress the return type of conversion operators, which always duplicates the target type.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??BConstOps@@QAE?BHXZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_CONVERSION_OPERATOR_TARGET).unwrap();
+        /// assert_eq!(without_flag, "public: int const __thiscall ConstOps::operator int const(void)");
+        /// assert_eq!(with_flag,    "public: __thiscall ConstOps::operator int const(void)");
+        /// ```
+        const NO_CONVERSION_OPERATOR_TARGET = 1 << 14;
+
+        /// Separate nested names with `.` instead of `::`.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?M@?1??L@N@@YAHXZ@4HA";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::SPLIT_NESTED_NAME_SEPARATOR).unwrap();
+        /// assert_eq!(without_flag, "int `int __cdecl N::L(void)'::`2'::M");
+        /// assert_eq!(with_flag,    "int `int __cdecl N.L(void)'.`2'.M");
+        /// ```
+        const SPLIT_NESTED_NAME_SEPARATOR = 1 << 15;
+
+        /// Suppress the return type of the primary declaration only when it is `void`. Function pointer types are unaffected.
+        /// See also [`NO_RETURN_TYPE`](Self::NO_RETURN_TYPE).
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?foo@@YAXH@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::DROP_VOID_RETURN).unwrap();
+        /// assert_eq!(without_flag, "void __cdecl foo(int)");
+        /// assert_eq!(with_flag,    "__cdecl foo(int)");
+        /// ```
+        const DROP_VOID_RETURN = 1 << 16;
+
+        /// Output integer literals (such as non-type template arguments and array bounds) greater than 255 in hexadecimal.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??0?$IntTemplate@$0PPPP@@@QEAA@XZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::CANONICAL_INTEGER_LITERALS).unwrap();
+        /// assert_eq!(without_flag, "public: __cdecl IntTemplate<65535>::IntTemplate<65535>(void)");
+        /// assert_eq!(with_flag,    "public: __cdecl IntTemplate<0xFFFF>::IntTemplate<0xFFFF>(void)");
+        /// ```
+        const CANONICAL_INTEGER_LITERALS = 1 << 17;
+
+        /// Match the behaviour of `llvm-undname`, which this crate's default rendering is based on. This disables the
+        /// extensions this crate understands on top of it, which `llvm-undname` either rejects or misreads:
+        /// * Objective-C symbols passed through with an `[objc]` label.
+        /// * Delay-load import thunks and helpers, labeled with `[delay import]`.
+        /// * Rust symbols passed through with a `[rust]` label.
+        /// * Thread-safe local static helpers, e.g. `_Init_thread_header` and `$TSS0` counters.
+        /// * Compiler runtime helpers, e.g. `__guard_dispatch_icall_fptr` and `__chkstk`, labeled with `[runtime helper]`.
+        /// * Profile instrumentation symbols, e.g. `__profc_`, demangled with an annotation.
+        /// * Bare type names without their leading `.`, e.g. `?AVfoo@@`.
+        /// * Throw info (`_TI`) and catchable type array (`_CTA`) symbols.
+        /// * Constant pool symbols, e.g. `__real@`.
+        /// * C++/CLI generic instantiations, which are otherwise rendered with a `[generic]` label.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "_TI1?AVexception@std@@";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::LLVM_COMPAT);
+        /// assert_eq!(without_flag, "`throw info for class std::exception'");
+        /// assert!(with_flag.is_err());
+        /// ```
+        const LLVM_COMPAT = 1 << 18;
+
+        /// Output each parameter of the primary declaration on its own line, while keeping the parameters of any function
+        /// pointers inline. This is useful for displaying complex signatures.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?h3@@YAP6APAHPAH0@ZP6APAH00@Z10@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::SHOW_FUNCTION_POINTER_PARAMS_INLINE).unwrap();
+        /// assert_eq!(without_flag, "int * (__cdecl * __cdecl h3(int * (__cdecl *)(int *, int *), int * (__cdecl *)(int *, int *), int *))(int *, int *)");
+        /// assert_eq!(with_flag,    "int * (__cdecl * __cdecl h3(\n    int * (__cdecl *)(int *, int *),\n    int * (__cdecl *)(int *, int *),\n    int *\n))(int *, int *)");
+        /// ```
+        const SHOW_FUNCTION_POINTER_PARAMS_INLINE = 1 << 19;
+
+        /// Output the descriptions of RTTI symbols without surrounding them in quotes.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??_R0?AUBase@@@8";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_RTTI_QUOTE_DECORATION).unwrap();
+        /// assert_eq!(without_flag, "struct Base `RTTI Type Descriptor'");
+        /// assert_eq!(with_flag,    "struct Base RTTI Type Descriptor");
+        /// ```
+        const NO_RTTI_QUOTE_DECORATION = 1 << 20;
+
+        /// Output the implicit `this` parameter of non-static member functions as an explicit first parameter. The
+        /// parameter carries the cv-qualifiers, `__unaligned`, and `__restrict` of the method, but not its ref-qualifier,
+        /// since `this` is a pointer either way.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?foo@klass@@QEBAXH@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::RENDER_THIS_PARAMETER).unwrap();
+        /// assert_eq!(without_flag, "public: void __cdecl klass::foo(int) const");
+        /// assert_eq!(with_flag,    "public: void __cdecl klass::foo(klass const *this, int) const");
+        /// ```
+        const RENDER_THIS_PARAMETER = 1 << 21;
+
+        /// Elide the arguments of template instantiations, leaving only `<...>` behind the template name.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?foo_abc@@YAXV?$A@DV?$B@D@@V?$C@D@@@@@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::FLATTEN_NESTED_TEMPLATES).unwrap();
+        /// assert_eq!(without_flag, "void __cdecl foo_abc(class A<char, class B<char>, class C<char>>)");
+        /// assert_eq!(with_flag,    "void __cdecl foo_abc(class A<...>)");
+        /// ```
+        const FLATTEN_NESTED_TEMPLATES = 1 << 22;
+
+        /// Output tags (`class`/`struct`/`union`/`enum`) inside template arguments, even when [`NO_TAG_SPECIFIER`](Self::NO_TAG_SPECIFIER) suppresses them everywhere else.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?x@@3PEAV?$tmpl@VA@@@@EA";
+        /// let without_flag = undname::demangle(input, Flags::NO_TAG_SPECIFIER).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_TAG_SPECIFIER | Flags::EMIT_TAG_IN_TEMPLATE_ARGS).unwrap();
+        /// assert_eq!(without_flag, "tmpl<A> *x");
+        /// assert_eq!(with_flag,    "tmpl<class A> *x");
+        /// ```
+        const EMIT_TAG_IN_TEMPLATE_ARGS = 1 << 23;
+
+        /// Output the indices of local scopes counting from zero, rather than from one like MSVC does.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?M@?0??L@@YAHXZ@4HA";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NUMERIC_SCOPE_FROM_ZERO).unwrap();
+        /// assert_eq!(without_flag, "int `int __cdecl L(void)'::`1'::M");
+        /// assert_eq!(with_flag,    "int `int __cdecl L(void)'::`0'::M");
+        /// ```
+        const NUMERIC_SCOPE_FROM_ZERO = 1 << 24;
+
+        /// Replace the numbers the compiler assigns to lambdas with a placeholder, while keeping local scope indices.
+        /// This is implied by [`BUILD_STABLE_NAMES`](Self::BUILD_STABLE_NAMES).
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??R<lambda_1>@?0??main@@YAHXZ@QBE@XZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::STABLE_LAMBDA_NUMBERS).unwrap();
+        /// assert_eq!(without_flag, "public: __thiscall `int __cdecl main(void)'::`1'::<lambda_1>::operator()(void) const");
+        /// assert_eq!(with_flag,    "public: __thiscall `int __cdecl main(void)'::`1'::<lambda>::operator()(void) const");
+        /// ```
+        const STABLE_LAMBDA_NUMBERS = 1 << 25;
+
+        /// Output the `const`/`volatile` qualifiers of builtin and tag types before the type, rather than after it.
+        /// Qualifiers which apply to pointers, such as `char *const`, are unaffected since they can only follow the `*`.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?foo_pbd@@YAXPBD@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::WEST_CONST).unwrap();
+        /// assert_eq!(without_flag, "void __cdecl foo_pbd(char const *)");
+        /// assert_eq!(with_flag,    "void __cdecl foo_pbd(const char *)");
+        /// ```
+        const WEST_CONST = 1 << 26;
+
+        /// Abbreviate common `std` templates whose trailing template arguments are all defaulted.
+        /// `std::basic_string` and `std::basic_string_view` over the standard character traits and allocator
+        /// are replaced with their typedefs, e.g. `std::string`, and the default allocator is dropped from
+        /// `std::vector`, `std::deque`, `std::list`, and `std::forward_list`.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?f@@YAXAEBV?$vector@V?$basic_string@DU?$char_traits@D@std@@V?$allocator@D@2@@std@@V?$allocator@V?$basic_string@DU?$char_traits@D@std@@V?$allocator@D@2@@std@@@2@@std@@@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::ABBREVIATE_STD).unwrap();
+        /// assert_eq!(without_flag, "void __cdecl f(class std::vector<class std::basic_string<char, struct std::char_traits<char>, class std::allocator<char>>, class std::allocator<class std::basic_string<char, struct std::char_traits<char>, class std::allocator<char>>>> const &)");
+        /// assert_eq!(with_flag,    "void __cdecl f(class std::vector<class std::string> const &)");
+        /// ```
+        const ABBREVIATE_STD = 1 << 27;
+
+        /// Output `char8_t` as its underlying type, `unsigned char`, for readers which predate C++20.
+        /// `char16_t` and `char32_t` are unaffected, since they have been builtin types since C++11.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?f@@YAX_Q@Z";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::CHAR8_AS_UNSIGNED_CHAR).unwrap();
+        /// assert_eq!(without_flag, "void __cdecl f(char8_t)");
+        /// assert_eq!(with_flag,    "void __cdecl f(unsigned char)");
+        /// ```
+        const CHAR8_AS_UNSIGNED_CHAR = 1 << 28;
+
+        /// Suppress the modifiers on the `this` type other than `const` (`volatile`/`__restrict`/`__unaligned`) from
+        /// being included in the output. Ref-qualifiers are kept. [`NO_THISTYPE`](Self::NO_THISTYPE) takes precedence.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?world@hello@@QEDAXXZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::TRAILING_CONST_ON_METHODS_ONLY).unwrap();
+        /// assert_eq!(without_flag, "public: void __cdecl hello::world(void) const volatile");
+        /// assert_eq!(with_flag,    "public: void __cdecl hello::world(void) const");
+        /// ```
+        const TRAILING_CONST_ON_METHODS_ONLY = 1 << 29;
+
+        /// Reject string literals whose mangled CRC does not match their contents, which indicates a corrupted symbol.
+        /// Literals which were truncated when mangled can't be checked, and are always accepted.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "??_C@_02PCEFGMJA@hi?$AA@";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::VALIDATE_STRING_CRC);
+        /// assert_eq!(without_flag, "\"hi\"");
+        /// assert!(with_flag.is_err());
+        /// ```
+        const VALIDATE_STRING_CRC = 1 << 30;
+
+        /// Render deduced return types as `auto` and `decltype(auto)`, instead of the `<auto>` and `<decltype-auto>`
+        /// placeholders used by MSVC.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?FunctionWithLocalType@@YA?A?<auto>@@XZ";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_AUTO_PLACEHOLDER).unwrap();
+        /// assert_eq!(without_flag, "<auto> __cdecl FunctionWithLocalType(void)");
+        /// assert_eq!(with_flag, "auto __cdecl FunctionWithLocalType(void)");
+        /// ```
+        const NO_AUTO_PLACEHOLDER = 1 << 31;
+
+        /// Disable expansion of calling conventions within the types of variables, while keeping them on functions.
+        /// ```rust
+        /// use undname::Flags;
+        /// let input = "?x@@3P6AHMNH@ZEA";
+        /// let without_flag = undname::demangle(input, Flags::default()).unwrap();
+        /// let with_flag = undname::demangle(input, Flags::NO_CC_ON_DATA).unwrap();
+        /// assert_eq!(without_flag, "int (__cdecl *x)(float, double, int)");
+        /// assert_eq!(with_flag, "int (*x)(float, double, int)");
+        /// ```
+        const NO_CC_ON_DATA = 1 << 32;
     }
 }
 
 impl Flags {
+    /// Output only the name for the primary declaration, without any arguments. This is the same as [`SIGNATURE_ONLY`](Self::SIGNATURE_ONLY).
+    /// ```rust
+    /// use undname::Flags;
+    /// let result = undname::demangle("?func@MyClass@@UEAAHHH@Z", Flags::name_and_no_args()).unwrap();
+    /// assert_eq!(result, "MyClass::func");
+    /// ```
+    #[must_use]
+    pub const fn name_and_no_args() -> Self {
+        Self::SIGNATURE_ONLY
+    }
+
+    /// Suppress everything except the name and the parameter list of the primary declaration.
+    /// ```rust
+    /// use undname::Flags;
+    /// let result = undname::demangle("?func@MyClass@@UEBAPEAUS@@PEAVT@@@Z", Flags::minimal()).unwrap();
+    /// assert_eq!(result, "MyClass::func(T *)");
+    /// ```
+    #[must_use]
+    pub const fn minimal() -> Self {
+        Self::from_bits_retain(
+            Self::NO_CALLING_CONVENTION.bits()
+                | Self::NO_TAG_SPECIFIER.bits()
+                | Self::NO_ACCESS_SPECIFIER.bits()
+                | Self::NO_MEMBER_TYPE.bits()
+                | Self::NO_RETURN_TYPE.bits()
+                | Self::NO_VARIABLE_TYPE.bits()
+                | Self::NO_THISTYPE.bits()
+                | Self::NO_MS_KEYWORDS.bits(),
+        )
+    }
+
+    /// Suppress the Microsoft specific decorations of the output, while keeping all of its types.
+    /// ```rust
+    /// use undname::Flags;
+    /// let result = undname::demangle("?func@MyClass@@UEBAPEAUS@@PEAVT@@@Z", Flags::compact()).unwrap();
+    /// assert_eq!(result, "S * MyClass::func(T *) const");
+    /// ```
+    #[must_use]
+    pub const fn compact() -> Self {
+        Self::from_bits_retain(
+            Self::NO_CALLING_CONVENTION.bits()
+                | Self::NO_TAG_SPECIFIER.bits()
+                | Self::NO_ACCESS_SPECIFIER.bits()
+                | Self::NO_MEMBER_TYPE.bits()
+                | Self::NO_MS_KEYWORDS.bits(),
+        )
+    }
+
     #[must_use]
     fn no_calling_convention(self) -> bool {
         self.contains(Self::NO_CALLING_CONVENTION)
@@ -381,6 +1029,139 @@ impl Flags {
     fn name_only(self) -> bool {
         self.contains(Self::NAME_ONLY)
     }
+
+    #[must_use]
+    fn size_t_aliases(self) -> bool {
+        self.contains(Self::SIZE_T_ALIASES)
+    }
+
+    #[must_use]
+    fn no_arguments(self) -> bool {
+        self.contains(Self::NO_ARGUMENTS)
+    }
+
+    #[must_use]
+    fn strict_utf8_identifiers(self) -> bool {
+        self.contains(Self::STRICT_UTF8_IDENTIFIERS)
+    }
+
+    #[must_use]
+    fn no_rtti_quote_decoration(self) -> bool {
+        self.contains(Self::NO_RTTI_QUOTE_DECORATION)
+    }
+
+    #[must_use]
+    fn render_this_parameter(self) -> bool {
+        self.contains(Self::RENDER_THIS_PARAMETER)
+    }
+
+    #[must_use]
+    fn flatten_nested_templates(self) -> bool {
+        self.contains(Self::FLATTEN_NESTED_TEMPLATES)
+    }
+
+    #[must_use]
+    fn emit_tag_in_template_args(self) -> bool {
+        self.contains(Self::EMIT_TAG_IN_TEMPLATE_ARGS)
+    }
+
+    #[must_use]
+    fn numeric_scope_from_zero(self) -> bool {
+        self.contains(Self::NUMERIC_SCOPE_FROM_ZERO)
+    }
+
+    #[must_use]
+    fn west_const(self) -> bool {
+        self.contains(Self::WEST_CONST)
+    }
+
+    #[must_use]
+    fn abbreviate_std(self) -> bool {
+        self.contains(Self::ABBREVIATE_STD)
+    }
+
+    #[must_use]
+    fn char8_as_unsigned_char(self) -> bool {
+        self.contains(Self::CHAR8_AS_UNSIGNED_CHAR)
+    }
+
+    #[must_use]
+    fn trailing_const_on_methods_only(self) -> bool {
+        self.contains(Self::TRAILING_CONST_ON_METHODS_ONLY)
+    }
+
+    #[must_use]
+    fn validate_string_crc(self) -> bool {
+        self.contains(Self::VALIDATE_STRING_CRC)
+    }
+
+    #[must_use]
+    fn no_auto_placeholder(self) -> bool {
+        self.contains(Self::NO_AUTO_PLACEHOLDER)
+    }
+
+    #[must_use]
+    fn no_cc_on_data(self) -> bool {
+        self.contains(Self::NO_CC_ON_DATA)
+    }
+
+    #[must_use]
+    fn stable_lambda_numbers(self) -> bool {
+        self.intersects(Self::STABLE_LAMBDA_NUMBERS | Self::BUILD_STABLE_NAMES)
+    }
+
+    #[must_use]
+    fn rtti_quotes(self) -> (&'static str, &'static str) {
+        if self.no_rtti_quote_decoration() {
+            ("", "")
+        } else {
+            ("`", "'")
+        }
+    }
+
+    #[must_use]
+    fn show_function_pointer_params_inline(self) -> bool {
+        self.contains(Self::SHOW_FUNCTION_POINTER_PARAMS_INLINE)
+    }
+
+    #[must_use]
+    fn llvm_compat(self) -> bool {
+        self.contains(Self::LLVM_COMPAT)
+    }
+
+    #[must_use]
+    fn build_stable_names(self) -> bool {
+        self.contains(Self::BUILD_STABLE_NAMES)
+    }
+
+    #[must_use]
+    fn no_conversion_operator_target(self) -> bool {
+        self.contains(Self::NO_CONVERSION_OPERATOR_TARGET)
+    }
+
+    #[must_use]
+    fn split_nested_name_separator(self) -> bool {
+        self.contains(Self::SPLIT_NESTED_NAME_SEPARATOR)
+    }
+
+    #[must_use]
+    fn drop_void_return(self) -> bool {
+        self.contains(Self::DROP_VOID_RETURN)
+    }
+
+    #[must_use]
+    fn canonical_integer_literals(self) -> bool {
+        self.contains(Self::CANONICAL_INTEGER_LITERALS)
+    }
+
+    #[must_use]
+    fn nested_name_separator(self) -> &'static str {
+        if self.split_nested_name_separator() {
+            "."
+        } else {
+            "::"
+        }
+    }
 }
 
 /// Demangles a Microsoft symbol stored in `mangled_name`.
@@ -390,15 +1171,370 @@ impl Flags {
 /// assert_eq!(result, "struct hello __cdecl world(void)");
 /// ```
 pub fn demangle(mangled_name: &str, flags: Flags) -> Result<String> {
-    let mut result = String::default();
-    demangle_into(mangled_name, flags, &mut result)?;
-    Ok(result)
+    demangle_to(mangled_name, flags, String::default())
 }
 
-/// See [`demangle`] for more info.
+/// Clears `result` and then demangles into it, reusing its allocation. `result` is left empty on failure.
+/// Prefer this over [`demangle`] when demangling many symbols in a loop. See [`demangle`] for more info.
+/// ```rust
+/// use undname::Flags;
+/// let mut result = String::new();
+/// for (input, expected) in [
+///     ("?world@@YA?AUhello@@XZ", "struct hello __cdecl world(void)"),
+///     ("?x@@3HA", "int x"),
+/// ] {
+///     undname::demangle_into(input, Flags::default(), &mut result).unwrap();
+///     assert_eq!(result, expected);
+/// }
+/// ```
 pub fn demangle_into(mangled_name: &str, flags: Flags, result: &mut String) -> Result<()> {
+    demangle_to(mangled_name, flags, result)
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, reserving `capacity` bytes for the result up front.
+/// Demangled names are typically several times longer than their mangled form, so reserving enough space avoids
+/// reallocating the result as it grows. See [`demangle`] for more info.
+/// ```rust
+/// use undname::Flags;
+/// let input = "?world@@YA?AUhello@@XZ";
+/// let result = undname::demangle_with_capacity(input, Flags::default(), input.len() * 4).unwrap();
+/// assert_eq!(result, "struct hello __cdecl world(void)");
+/// ```
+pub fn demangle_with_capacity(mangled_name: &str, flags: Flags, capacity: usize) -> Result<String> {
+    demangle_to(mangled_name, flags, String::with_capacity(capacity))
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, returning it alongside the original input.
+/// This is convenient when both forms are needed later, e.g. for logging. See [`demangle`] for more info.
+/// ```rust
+/// use undname::Flags;
+/// let (mangled, demangled) = undname::demangle_pair("?world@@YA?AUhello@@XZ", Flags::default()).unwrap();
+/// assert_eq!(mangled, "?world@@YA?AUhello@@XZ");
+/// assert_eq!(demangled, "struct hello __cdecl world(void)");
+/// ```
+pub fn demangle_pair(mangled_name: &str, flags: Flags) -> Result<(&str, String)> {
+    let demangled_name = demangle(mangled_name, flags)?;
+    Ok((mangled_name, demangled_name))
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, followed by the original input in brackets.
+/// See [`demangle`] for more info.
+/// ```rust
+/// use undname::Flags;
+/// let result = undname::demangle_annotated("?world@@YA?AUhello@@XZ", Flags::default()).unwrap();
+/// assert_eq!(result, "struct hello __cdecl world(void) [?world@@YA?AUhello@@XZ]");
+/// ```
+pub fn demangle_annotated(mangled_name: &str, flags: Flags) -> Result<String> {
+    let demangled_name = demangle(mangled_name, flags)?;
+    Ok(format!("{demangled_name} [{mangled_name}]"))
+}
+
+/// Demangles each Microsoft symbol in `mangled_names` into the corresponding slot of `results`, reusing the
+/// allocations of any strings already in it. `results` is resized to hold exactly one string per symbol.
+/// Returns the number of symbols which failed to demangle, whose slots are left empty.
+/// ```rust
+/// use undname::Flags;
+/// let mut results = Vec::new();
+/// let failures = undname::demangle_all_into(["?x@@3HA", "?x@@3", "?y@@3DA"], Flags::default(), &mut results);
+/// assert_eq!(failures, 1);
+/// assert_eq!(results, ["int x", "", "char y"]);
+///
+/// // the strings from the previous batch are reused
+/// let failures = undname::demangle_all_into(["?z@@3MA"], Flags::default(), &mut results);
+/// assert_eq!(failures, 0);
+/// assert_eq!(results, ["float z"]);
+/// ```
+pub fn demangle_all_into<'a, I>(mangled_names: I, flags: Flags, results: &mut Vec<String>) -> usize
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut len = 0;
+    let mut failures = 0;
+    for mangled_name in mangled_names {
+        if len == results.len() {
+            results.push(String::new());
+        }
+        if demangle_into(mangled_name, flags, &mut results[len]).is_err() {
+            failures += 1;
+        }
+        len += 1;
+    }
+    results.truncate(len);
+    failures
+}
+
+/// Demangles a Microsoft symbol stored in `mangled_name` into the given `sink`. See [`DemangleSink`] for the supported sinks.
+/// ```rust
+/// use std::io;
+/// use undname::Flags;
+/// let input = "?world@@YA?AUhello@@XZ";
+///
+/// let result = undname::demangle_to(input, Flags::default(), String::new()).unwrap();
+/// assert_eq!(result, "struct hello __cdecl world(void)");
+///
+/// let mut bytes = Vec::new();
+/// undname::demangle_to(input, Flags::default(), &mut bytes).unwrap();
+/// assert_eq!(bytes, b"struct hello __cdecl world(void)");
+///
+/// let mut stream = io::Cursor::new(Vec::new());
+/// undname::demangle_to(input, Flags::default(), &mut stream as &mut dyn io::Write).unwrap();
+/// assert_eq!(stream.into_inner(), b"struct hello __cdecl world(void)");
+/// ```
+pub fn demangle_to<S: DemangleSink>(
+    mangled_name: &str,
+    flags: Flags,
+    sink: S,
+) -> Result<S::Output> {
+    sink.demangle(mangled_name, flags)
+}
+
+/// Demangles each line of `input` as a Microsoft symbol, and writes the results to `output`, one per line.
+/// Every line keeps its original line ending, whether that is `\n`, `\r\n`, or none at all for the last line.
+/// Lines which fail to demangle, including those which aren't valid UTF-8, are written back out unchanged.
+/// ```rust
+/// use undname::Flags;
+/// let input = "?world@@YA?AUhello@@XZ\r\nnot a symbol\n?x@@3HA";
+/// let mut output = Vec::new();
+/// undname::demangle_stream(input.as_bytes(), &mut output, Flags::default()).unwrap();
+/// assert_eq!(output, b"struct hello __cdecl world(void)\r\nnot a symbol\nint x");
+/// ```
+pub fn demangle_stream<R: io::BufRead, W: io::Write>(
+    mut input: R,
+    mut output: W,
+    flags: Flags,
+) -> io::Result<()> {
+    let mut alloc = Bump::default();
+    let mut buffer = Vec::new();
+    let mut result = Vec::new();
+    while input.read_until(b'\n', &mut buffer)? != 0 {
+        // strip the line ending the same way BufRead::lines does, but keep it for the output
+        let line = match buffer.strip_suffix(b"\n") {
+            Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+            None => &buffer,
+        };
+        let line_ending = &buffer[line.len()..];
+        result.clear();
+        let demangled = str::from_utf8(line).is_ok_and(|line| {
+            Demangler::new(line, flags, &alloc)
+                .parse_into_bytes(&mut result)
+                .is_ok()
+        });
+        if demangled {
+            output.write_all(&result)?;
+        } else {
+            output.write_all(line)?;
+        }
+        output.write_all(line_ending)?;
+        buffer.clear();
+        alloc.reset();
+    }
+    Ok(())
+}
+
+/// Demangles a Microsoft symbol stored in `mangled_name`, and writes the result directly into `result`.
+/// Unlike [`demangle_into`], if an error occurs while writing, then `result` may be left holding partial output.
+/// ```rust
+/// use std::fmt;
+/// use undname::Flags;
+///
+/// struct Demangled<'a>(&'a str);
+///
+/// impl fmt::Display for Demangled<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         undname::demangle_fmt(self.0, Flags::default(), f).map_err(|_| fmt::Error)
+///     }
+/// }
+///
+/// let result = format!("{}", Demangled("?world@@YA?AUhello@@XZ"));
+/// assert_eq!(result, "struct hello __cdecl world(void)");
+/// ```
+pub fn demangle_fmt<W: fmt::Write + ?Sized>(
+    mangled_name: &str,
+    flags: Flags,
+    result: &mut W,
+) -> Result<()> {
     let alloc = Bump::default();
     let d = Demangler::new(mangled_name, flags, &alloc);
-    result.clear();
-    d.parse_into(result)
+    d.parse_into_fmt(result)
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, after seeding its name back-reference table with `name_seeds`.
+/// This lets a fragment split off of a larger symbol resolve back-references (`0`-`9`) to names which were
+/// mangled before it. As when demangling, duplicate names are only memorized once and only the first 10 are kept.
+/// ```rust
+/// use undname::Flags;
+/// let result = undname::demangle_with_seeds("?f@0@YAXXZ", Flags::default(), &["ns"]).unwrap();
+/// assert_eq!(result, "void __cdecl ns::f(void)");
+/// ```
+pub fn demangle_with_seeds(
+    mangled_name: &str,
+    flags: Flags,
+    name_seeds: &[&str],
+) -> Result<String> {
+    let alloc = Bump::default();
+    let mut d = Demangler::new(mangled_name, flags, &alloc);
+    d.seed_names(name_seeds)?;
+    let mut result = String::new();
+    d.parse_into(&mut result)?;
+    Ok(result)
+}
+
+/// Checks that the Microsoft symbol stored in `mangled_name` is well-formed, without rendering it.
+/// Only parsing is checked: limits which are enforced while rendering, such as the cap on output length, are not, so
+/// [`demangle`] may still fail on a symbol which passes validation.
+/// ```rust
+/// use undname::Flags;
+/// assert!(undname::validate("?world@@YA?AUhello@@XZ", Flags::default()).is_ok());
+/// assert!(undname::validate("?world@@YA?AUhello@@", Flags::default()).is_err());
+/// ```
+pub fn validate(mangled_name: &str, flags: Flags) -> Result<()> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, flags, &alloc);
+    d.validate()
+}
+
+/// Determines the kind of entity the Microsoft symbol stored in `mangled_name` refers to, without rendering it.
+/// ```rust
+/// use undname::SymbolKind;
+/// assert_eq!(undname::classify("?world@@YA?AUhello@@XZ").unwrap(), SymbolKind::Function);
+/// assert_eq!(undname::classify("??_7Base@@6B@").unwrap(), SymbolKind::SpecialTable);
+/// ```
+pub fn classify(mangled_name: &str) -> Result<SymbolKind> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, Flags::default(), &alloc);
+    d.classify()
+}
+
+/// Extracts metadata about the Microsoft symbol stored in `mangled_name`, without rendering it.
+/// ```rust
+/// use undname::SymbolKind;
+/// let info = undname::symbol_info("??_R0?AUBase@@@8BA@").unwrap();
+/// assert_eq!(info.kind, SymbolKind::Rtti);
+/// assert_eq!(info.type_descriptor_extra_bytes, Some(16));
+/// assert_eq!(undname::symbol_info("??_R0?AUBase@@@8").unwrap().type_descriptor_extra_bytes, None);
+///
+/// use undname::ComparisonCategory;
+/// let info = undname::symbol_info("??__MS@@QEAA?AVstrong_ordering@std@@AEBU0@@Z").unwrap();
+/// assert_eq!(info.three_way_comparison, Some(ComparisonCategory::StrongOrdering));
+///
+/// use undname::StructorKind;
+/// let info = undname::symbol_info("??1?$klass@H@ns@@QEAA@XZ").unwrap();
+/// assert_eq!(info.structor_kind, Some(StructorKind::Destructor));
+/// assert_eq!(info.structor_class.unwrap(), "ns::klass<int>");
+///
+/// use undname::VariableNameKind;
+/// let info = undname::symbol_info(".?AUBase@@").unwrap();
+/// assert_eq!(info.variable_name, Some(VariableNameKind::TypeDescriptor));
+/// ```
+pub fn symbol_info(mangled_name: &str) -> Result<SymbolInfo> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, Flags::default(), &alloc);
+    d.symbol_info()
+}
+
+/// Demangles the scopes enclosing the unqualified name of the Microsoft symbol stored in `mangled_name`, outermost first.
+/// Function-local scopes are rendered as a single scope, e.g. ``"`void __cdecl f(void)'::`2'"``.
+/// ```rust
+/// let result = undname::namespace_path("?spam@NB@PR13207@@YAXV?$Y@VX@NA@PR13207@@@NA@2@@Z").unwrap();
+/// assert_eq!(result, ["PR13207", "NB"]);
+/// assert!(undname::namespace_path("?world@@YAXXZ").unwrap().is_empty());
+/// ```
+pub fn namespace_path(mangled_name: &str) -> Result<Vec<String>> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, Flags::default(), &alloc);
+    d.namespace_path()
+}
+
+/// Demangles the qualified name of the Microsoft function symbol stored in `mangled_name`, followed by its number of
+/// parameters, e.g. `ns::foo(2)`. Overloads which only differ in the types of their parameters share the same key,
+/// which is useful for grouping overload sets. Variadic functions are suffixed with `...`, e.g. `printf(1, ...)`.
+/// Returns `None` if the symbol is not a function.
+/// ```rust
+/// use undname::Flags;
+/// assert_eq!(undname::overload_key("?foo@ns@@YAXHH@Z", Flags::default()).unwrap().unwrap(), "ns::foo(2)");
+/// assert_eq!(undname::overload_key("?foo@ns@@YAXMN@Z", Flags::default()).unwrap().unwrap(), "ns::foo(2)");
+/// assert_eq!(undname::overload_key("?x@@3HA", Flags::default()).unwrap(), None);
+/// ```
+pub fn overload_key(mangled_name: &str, flags: Flags) -> Result<Option<String>> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, flags, &alloc);
+    d.overload_key()
+}
+
+/// Demangles the unqualified name of the Microsoft symbol stored in `mangled_name`, without its enclosing scopes.
+/// Symbols without a name, such as string literals, produce an empty string. See [`namespace_path`] for the scopes.
+/// ```rust
+/// let result = undname::leaf_name("?spam@NB@PR13207@@YAXV?$Y@VX@NA@PR13207@@@NA@2@@Z").unwrap();
+/// assert_eq!(result, "spam");
+/// assert_eq!(undname::leaf_name("??1klass@@QEAA@XZ").unwrap(), "~klass");
+/// ```
+pub fn leaf_name(mangled_name: &str) -> Result<String> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, Flags::default(), &alloc);
+    d.leaf_name()
+}
+
+/// Demangles the template arguments of the unqualified name of the Microsoft symbol stored in `mangled_name`.
+/// Returns `None` if the name is not a template instantiation.
+/// ```rust
+/// use undname::Flags;
+/// let input = "??0?$vector@HV?$allocator@H@std@@@std@@QEAA@XZ";
+/// let result = undname::template_arguments(input, Flags::default()).unwrap();
+/// assert_eq!(result.unwrap(), ["int", "class std::allocator<int>"]);
+/// assert_eq!(undname::template_arguments("?world@@YAXXZ", Flags::default()).unwrap(), None);
+/// ```
+pub fn template_arguments(mangled_name: &str, flags: Flags) -> Result<Option<Vec<String>>> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, flags, &alloc);
+    d.template_arguments()
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, and returns the contents of its back-reference tables
+/// rendered using `flags`. This is useful for troubleshooting symbols which make heavy use of back-references.
+/// ```rust
+/// use undname::Flags;
+/// let backrefs = undname::dump_backrefs("?f@ns@@YAXPAUS@1@0@Z", Flags::default()).unwrap();
+/// assert_eq!(backrefs.names, ["f", "ns", "S"]);
+/// assert_eq!(backrefs.function_params, ["struct ns::S *"]);
+/// ```
+#[cfg(feature = "debug")]
+pub fn dump_backrefs(mangled_name: &str, flags: Flags) -> Result<Backrefs> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, flags, &alloc);
+    d.dump_backrefs()
+}
+
+/// Demangles the Microsoft symbol stored in `mangled_name`, and reports statistics about the work it took.
+/// This is useful for finding which symbols are expensive to demangle. See [`demangle`] for more info.
+/// ```rust
+/// use undname::Flags;
+/// let (result, stats) = undname::demangle_with_stats("?f@ns@@YAXPAUS@1@0@Z", Flags::default()).unwrap();
+/// assert_eq!(result, "void __cdecl ns::f(struct ns::S *, struct ns::S *)");
+/// assert_eq!(stats.max_name_backrefs, 3);
+/// assert_eq!(stats.max_param_backrefs, 1);
+/// assert_eq!(stats.output_len, result.len());
+/// ```
+#[cfg(feature = "stats")]
+pub fn demangle_with_stats(mangled_name: &str, flags: Flags) -> Result<(String, DemangleStats)> {
+    let alloc = Bump::default();
+    let d = Demangler::new(mangled_name, flags, &alloc);
+    d.parse_with_stats()
+}
+
+/// Guesses which compiler produced the Microsoft symbol stored in `mangled_name`.
+/// This is a heuristic: Clang only mangles names differently when it has to, e.g. for its `__clang` extension types,
+/// so most of its symbols are indistinguishable from those produced by MSVC.
+/// ```rust
+/// use undname::Producer;
+/// assert_eq!(undname::producer_hint("?world@@YA?AUhello@@XZ"), Producer::Msvc);
+/// assert_eq!(undname::producer_hint("?f@@YAXU?$_Complex@H@__clang@@@Z"), Producer::ClangCl);
+/// assert_eq!(undname::producer_hint("_ZN5hello5worldEv"), Producer::Unknown);
+/// ```
+#[must_use]
+pub fn producer_hint(mangled_name: &str) -> Producer {
+    match classify(mangled_name) {
+        Err(_) | Ok(SymbolKind::Foreign) => Producer::Unknown,
+        Ok(_) if mangled_name.contains("@__clang@@") => Producer::ClangCl,
+        Ok(_) => Producer::Msvc,
+    }
 }
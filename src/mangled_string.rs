@@ -244,8 +244,9 @@ impl<'string> MangledString<'string> {
     }
 
     pub(crate) fn is_pointer_type(self) -> bool {
-        if self.starts_with("$$Q") {
-            // foo &&
+        if self.starts_with("$$Q") || self.starts_with("$$R") {
+            // $$Q -> foo &&
+            // $$R -> foo && volatile
             true
         } else {
             // A -> foo &
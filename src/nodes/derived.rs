@@ -21,7 +21,9 @@ use crate::{
         QualifiedName,
         VariableSymbol,
     },
+    demangler::BufWriter,
     nodes::{
+        intermediate::TypeNodeConst,
         CallingConv,
         CharKind,
         Downcast as _,
@@ -32,12 +34,14 @@ use crate::{
         ISignatureNode,
         ISymbolNode,
         ITypeNode,
+        IdentifierNode,
         IntrinsicFunctionKind,
         PointerAffinity,
         PrimitiveKind,
         Qualifiers,
         Result,
         SignatureNode,
+        SpecialIntrinsicKind,
         StorageClass,
         TagKind,
         TypeNode,
@@ -87,6 +91,7 @@ impl WriteableTypeNode for PrimitiveTypeNode {
             PrimitiveKind::Char => "char",
             PrimitiveKind::Schar => "signed char",
             PrimitiveKind::Uchar => "unsigned char",
+            PrimitiveKind::Char8 if flags.char8_as_unsigned_char() => "unsigned char",
             PrimitiveKind::Char8 => "char8_t",
             PrimitiveKind::Char16 => "char16_t",
             PrimitiveKind::Char32 => "char32_t",
@@ -96,7 +101,9 @@ impl WriteableTypeNode for PrimitiveTypeNode {
             PrimitiveKind::Uint => "unsigned int",
             PrimitiveKind::Long => "long",
             PrimitiveKind::Ulong => "unsigned long",
+            PrimitiveKind::Int64 if flags.size_t_aliases() => "ptrdiff_t",
             PrimitiveKind::Int64 => "__int64",
+            PrimitiveKind::Uint64 if flags.size_t_aliases() => "size_t",
             PrimitiveKind::Uint64 => "unsigned __int64",
             PrimitiveKind::Wchar => "wchar_t",
             PrimitiveKind::Float => "float",
@@ -106,8 +113,14 @@ impl WriteableTypeNode for PrimitiveTypeNode {
             PrimitiveKind::Auto => "auto",
             PrimitiveKind::DecltypeAuto => "decltype(auto)",
         };
-        write!(ob, "{kind}")?;
-        self.quals.output(ob, flags, true, false)
+        if flags.west_const() {
+            self.quals.output(ob, flags, false, true)?;
+            write!(ob, "{kind}")?;
+            Ok(())
+        } else {
+            write!(ob, "{kind}")?;
+            self.quals.output(ob, flags, true, false)
+        }
     }
 
     fn output_post(&self, _: &NodeCache, _: &mut dyn Writer, _: OutputFlags) -> Result<()> {
@@ -178,7 +191,7 @@ impl FunctionSignatureNode {
         }
 
         if !flags.no_return_type() && (is_function_ptr || !flags.name_only()) {
-            if let Some(return_type) = self.return_type.map(|x| x.resolve(cache)) {
+            if let Some(return_type) = self.visible_return_type(cache, flags, is_function_ptr) {
                 return_type.output_pre(cache, ob, flags)?;
                 write!(ob, " ")?;
             }
@@ -203,32 +216,77 @@ impl FunctionSignatureNode {
         ob: &mut dyn Writer,
         flags: OutputFlags,
         is_function_ptr: bool,
+        one_per_line: bool,
+        this_class: Option<&NodeArrayNode>,
     ) -> Result<()> {
-        if (is_function_ptr || !flags.name_only()) && !self.function_class.no_parameter_list() {
+        let separator = if one_per_line { ",\n    " } else { ", " };
+
+        let show_arguments = is_function_ptr || (!flags.name_only() && !flags.no_arguments());
+        if show_arguments && !self.function_class.no_parameter_list() {
             write!(ob, "(")?;
-            if let Some(params) = self.params.map(|x| x.resolve(cache)) {
-                params.output(cache, ob, flags)?;
-            } else {
+            let params = self.params.map(|x| x.resolve(cache));
+            let has_params = params.is_some() || self.is_variadic;
+            if one_per_line && (has_params || this_class.is_some()) {
+                write!(ob, "\n    ")?;
+            }
+            if let Some(this_class) = this_class {
+                this_class.do_output(cache, ob, flags, flags.nested_name_separator())?;
+                if self.quals.is_const() {
+                    write!(ob, " const")?;
+                }
+                if self.quals.is_volatile() {
+                    write!(ob, " volatile")?;
+                }
+                let underscores = if flags.no_leading_underscores() {
+                    ""
+                } else {
+                    "__"
+                };
+                if self.quals.is_unaligned() && !flags.no_ms_keywords() {
+                    write!(ob, " {underscores}unaligned")?;
+                }
+                write!(ob, " *")?;
+                if self.quals.is_restrict() && !flags.no_ms_keywords() {
+                    write!(ob, "{underscores}restrict ")?;
+                }
+                write!(ob, "this")?;
+                if has_params {
+                    write!(ob, "{separator}")?;
+                }
+            }
+            if let Some(params) = params {
+                params.do_output(cache, ob, flags, separator)?;
+            } else if this_class.is_none() && !(one_per_line && self.is_variadic) {
                 write!(ob, "void")?;
             }
 
             if self.is_variadic {
-                if ob.last_char().is_some_and(|x| x != '(') {
-                    write!(ob, ", ")?;
+                // a variadic-only parameter list has already broken onto its own line
+                if ob
+                    .last_char()
+                    .is_some_and(|x| x != '(' && !(one_per_line && x == ' '))
+                {
+                    write!(ob, "{separator}")?;
                 }
                 write!(ob, "...")?;
             }
+            if one_per_line && (has_params || this_class.is_some()) {
+                writeln!(ob)?;
+            }
             write!(ob, ")")?;
         }
 
-        if !flags.no_this_type() && !flags.name_only() {
+        // the qualifiers and exception specification trail the parameter list, so they're hidden along with it
+        let show_declarator = show_arguments && !flags.name_only();
+        if !flags.no_this_type() && show_declarator {
             if self.quals.is_const() {
                 write!(ob, " const")?;
             }
-            if self.quals.is_volatile() {
+            let trailing_const_only = flags.trailing_const_on_methods_only();
+            if self.quals.is_volatile() && !trailing_const_only {
                 write!(ob, " volatile")?;
             }
-            if !flags.no_ms_keywords() {
+            if !flags.no_ms_keywords() && !trailing_const_only {
                 if self.quals.is_restrict() {
                     if flags.no_leading_underscores() {
                         write!(ob, " restrict")?;
@@ -246,11 +304,7 @@ impl FunctionSignatureNode {
             }
         }
 
-        if self.is_noexcept {
-            write!(ob, " noexcept")?;
-        }
-
-        if !flags.no_this_type() && !flags.name_only() {
+        if !flags.no_this_type() && show_declarator {
             match self.ref_qualifier {
                 Some(FunctionRefQualifier::Reference) => write!(ob, " &")?,
                 Some(FunctionRefQualifier::RValueReference) => write!(ob, " &&")?,
@@ -258,14 +312,43 @@ impl FunctionSignatureNode {
             }
         }
 
+        // the exception specification follows the ref-qualifier, as in C++
+        if self.is_noexcept && show_declarator {
+            write!(ob, " noexcept")?;
+        }
+
         if !flags.no_return_type() && !flags.name_only() {
-            if let Some(return_type) = self.return_type.map(|x| x.resolve(cache)) {
+            if let Some(return_type) = self.visible_return_type(cache, flags, is_function_ptr) {
                 return_type.output_post(cache, ob, flags)?;
             }
         }
 
         Ok(())
     }
+
+    #[must_use]
+    fn has_this_parameter(&self) -> bool {
+        !self.function_class.is_global() && !self.function_class.is_static()
+    }
+
+    #[must_use]
+    fn visible_return_type<'cache, 'alloc>(
+        &self,
+        cache: &'cache NodeCache<'alloc>,
+        flags: OutputFlags,
+        is_function_ptr: bool,
+    ) -> Option<TypeNodeConst<'cache, 'alloc>> {
+        let return_type = self.return_type.map(|x| x.resolve(cache))?;
+        let is_void = matches!(
+            return_type,
+            TypeNode::PrimitiveType(PrimitiveTypeNode {
+                prim_kind: PrimitiveKind::Void,
+                ..
+            })
+        );
+        // function pointer types would be ambiguous without their return type
+        (is_function_ptr || !flags.drop_void_return() || !is_void).then_some(return_type)
+    }
 }
 
 impl Default for FunctionSignatureNode {
@@ -301,7 +384,7 @@ impl WriteableTypeNode for FunctionSignatureNode {
         ob: &mut dyn Writer,
         flags: OutputFlags,
     ) -> Result<()> {
-        self.do_output_post(cache, ob, flags, false)
+        self.do_output_post(cache, ob, flags, false, false, None)
     }
 }
 
@@ -340,6 +423,8 @@ impl ThunkSignatureNode {
         ob: &mut dyn Writer,
         flags: OutputFlags,
         is_function_ptr: bool,
+        one_per_line: bool,
+        this_class: Option<&NodeArrayNode>,
     ) -> Result<()> {
         let ThisAdjustor {
             static_offset,
@@ -358,8 +443,14 @@ impl ThunkSignatureNode {
             }
         }
 
-        self.function_node
-            .do_output_post(cache, ob, flags, is_function_ptr)
+        self.function_node.do_output_post(
+            cache,
+            ob,
+            flags,
+            is_function_ptr,
+            one_per_line,
+            this_class,
+        )
     }
 }
 
@@ -394,7 +485,7 @@ impl WriteableTypeNode for ThunkSignatureNode {
         ob: &mut dyn Writer,
         flags: OutputFlags,
     ) -> Result<()> {
-        self.do_output_post(cache, ob, flags, false)
+        self.do_output_post(cache, ob, flags, false, false, None)
     }
 }
 
@@ -463,7 +554,7 @@ impl WriteableTypeNode for PointerTypeNode {
 
         if let Some(class_parent) = self.class_parent.map(|x| x.resolve(cache)) {
             class_parent.output(cache, ob, flags)?;
-            write!(ob, "::")?;
+            write!(ob, "{}", flags.nested_name_separator())?;
         }
 
         let affinity = self
@@ -491,10 +582,10 @@ impl WriteableTypeNode for PointerTypeNode {
         if let TypeNode::Signature(sig) = pointee {
             match sig {
                 SignatureNode::FunctionSignature(func) => {
-                    func.do_output_post(cache, ob, flags, true)
+                    func.do_output_post(cache, ob, flags, true, false, None)
                 }
                 SignatureNode::ThunkSignature(thunk) => {
-                    thunk.do_output_post(cache, ob, flags, true)
+                    thunk.do_output_post(cache, ob, flags, true, false, None)
                 }
             }
         } else {
@@ -510,6 +601,39 @@ pub(crate) struct TagTypeNode {
     pub(crate) tag: TagKind,
 }
 
+impl TagTypeNode {
+    // The SIMD vector types are declared as unions or structs, but are spelled like builtins.
+    #[must_use]
+    fn is_vector_intrinsic(&self, cache: &NodeCache) -> bool {
+        let components = self.qualified_name.resolve(cache).components.resolve(cache);
+        match components.nodes {
+            [node] => match node
+                .downcast::<IIdentifierNode>(cache)
+                .map(|x| x.resolve(cache))
+            {
+                Some(IdentifierNode::NamedIdentifier(x)) => {
+                    x.template_params.params.is_none()
+                        && matches!(
+                            x.name,
+                            "__m64"
+                                | "__m128"
+                                | "__m128d"
+                                | "__m128i"
+                                | "__m256"
+                                | "__m256d"
+                                | "__m256i"
+                                | "__m512"
+                                | "__m512d"
+                                | "__m512i"
+                        )
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
 impl WriteableNode for TagTypeNode {
     fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
         self.output_pair(cache, ob, flags)
@@ -518,7 +642,11 @@ impl WriteableNode for TagTypeNode {
 
 impl WriteableTypeNode for TagTypeNode {
     fn output_pre(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        if !flags.no_tag_specifier() && !flags.name_only() {
+        if flags.west_const() {
+            self.quals.output(ob, flags, false, true)?;
+        }
+
+        if !flags.no_tag_specifier() && !flags.name_only() && !self.is_vector_intrinsic(cache) {
             let tag = match self.tag {
                 TagKind::Class => "class",
                 TagKind::Struct => "struct",
@@ -532,7 +660,11 @@ impl WriteableTypeNode for TagTypeNode {
             .resolve(cache)
             .output(cache, ob, flags)?;
 
-        self.quals.output(ob, flags, true, false)
+        if flags.west_const() {
+            Ok(())
+        } else {
+            self.quals.output(ob, flags, true, false)
+        }
     }
 
     fn output_post(&self, _: &NodeCache, _: &mut dyn Writer, _: OutputFlags) -> Result<()> {
@@ -597,10 +729,42 @@ impl WriteableNode for ArrayTypeNode {
 
 impl WriteableTypeNode for ArrayTypeNode {
     fn output_pre(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        self.element_type
-            .resolve(cache)
-            .output_pre(cache, ob, flags)?;
-        self.quals.output(ob, flags, true, false)
+        let element_type = self.element_type.resolve(cache);
+        // the qualifiers of an array apply to its elements
+        let west_const = flags.west_const()
+            && matches!(
+                element_type,
+                TypeNode::PrimitiveType(_) | TypeNode::TagType(_)
+            );
+        if west_const {
+            self.quals.output(ob, flags, false, true)?;
+        }
+        if let TypeNode::Signature(sig) = element_type {
+            // Like a pointer to a function, the dimensions of an array of functions go inside
+            // the parentheses, along with the calling convention.
+            match sig {
+                SignatureNode::FunctionSignature(func) => {
+                    func.do_output_pre(cache, ob, flags, true)?;
+                }
+                SignatureNode::ThunkSignature(thunk) => {
+                    thunk.do_output_pre(cache, ob, flags, true)?;
+                }
+            }
+            super::output_space_if_necessary(ob)?;
+            write!(ob, "(")?;
+            if !flags.no_calling_convention() && !flags.no_ms_keywords() {
+                if let Some(call_convention) = sig.as_node().call_convention {
+                    call_convention.output(ob, flags)?;
+                }
+            }
+        } else {
+            element_type.output_pre(cache, ob, flags)?;
+        }
+        if west_const {
+            Ok(())
+        } else {
+            self.quals.output(ob, flags, true, false)
+        }
     }
 
     fn output_post(
@@ -612,9 +776,17 @@ impl WriteableTypeNode for ArrayTypeNode {
         write!(ob, "[")?;
         self.output_dimensions_impl(cache, ob, flags)?;
         write!(ob, "]")?;
-        self.element_type
-            .resolve(cache)
-            .output_post(cache, ob, flags)
+        match self.element_type.resolve(cache) {
+            TypeNode::Signature(SignatureNode::FunctionSignature(func)) => {
+                write!(ob, ")")?;
+                func.do_output_post(cache, ob, flags, true, false, None)
+            }
+            TypeNode::Signature(SignatureNode::ThunkSignature(thunk)) => {
+                write!(ob, ")")?;
+                thunk.do_output_post(cache, ob, flags, true, false, None)
+            }
+            element_type => element_type.output_post(cache, ob, flags),
+        }
     }
 }
 
@@ -632,7 +804,21 @@ impl WriteableNode for CustomTypeNode {
 
 impl WriteableTypeNode for CustomTypeNode {
     fn output_pre(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        self.identifier.resolve(cache).output(cache, ob, flags)
+        let identifier = self.identifier.resolve(cache);
+        if flags.no_auto_placeholder() {
+            if let IdentifierNode::NamedIdentifier(x) = identifier {
+                let placeholder = match x.name {
+                    "<auto>" => Some("auto"),
+                    "<decltype-auto>" => Some("decltype(auto)"),
+                    _ => None,
+                };
+                if let Some(placeholder) = placeholder {
+                    write!(ob, "{placeholder}")?;
+                    return Ok(());
+                }
+            }
+        }
+        identifier.output(cache, ob, flags)
     }
 
     fn output_post(&self, _: &NodeCache, _: &mut dyn Writer, _: OutputFlags) -> Result<()> {
@@ -641,30 +827,51 @@ impl WriteableTypeNode for CustomTypeNode {
 }
 
 #[derive(Clone, Copy, Default)]
-pub(crate) struct TemplateParameters(pub(crate) Option<NodeHandle<NodeArray>>);
+pub(crate) struct TemplateParameters {
+    pub(crate) params: Option<NodeHandle<NodeArray>>,
+    // C++/CLI generics are instantiated at runtime, unlike native templates
+    pub(crate) is_generic: bool,
+}
 
 impl TemplateParameters {
     fn output(self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
         if let Some(this) = self.map(|x| x.resolve(cache)) {
-            write!(ob, "<")?;
-            this.output(cache, ob, flags)?;
-            write!(ob, ">")?;
+            output_template_arguments(this, cache, ob, flags)?;
         }
         Ok(())
     }
 }
 
+fn output_template_arguments(
+    args: &NodeArrayNode,
+    cache: &NodeCache,
+    ob: &mut dyn Writer,
+    flags: OutputFlags,
+) -> Result<()> {
+    let mut flags = flags;
+    if flags.emit_tag_in_template_args() {
+        flags -= OutputFlags::NO_TAG_SPECIFIER;
+    }
+    write!(ob, "<")?;
+    if flags.flatten_nested_templates() && !args.nodes.is_empty() {
+        write!(ob, "...")?;
+    } else {
+        args.output(cache, ob, flags)?;
+    }
+    write!(ob, ">")
+}
+
 impl Deref for TemplateParameters {
     type Target = Option<NodeHandle<NodeArray>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.params
     }
 }
 
 impl DerefMut for TemplateParameters {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.params
     }
 }
 
@@ -739,11 +946,73 @@ impl WriteableNode for DynamicStructorIdentifierNode {
 pub(crate) struct NamedIdentifierNode<'alloc> {
     pub(crate) template_params: TemplateParameters,
     pub(crate) name: &'alloc str,
+
+    // Which RTTI structure this names, if it was synthesized for one, e.g. `RTTI Base Class Array'
+    pub(crate) rtti: Option<SpecialIntrinsicKind>,
+
+    // The identifier this back-reference was memorized from, which is rendered in its place
+    pub(crate) memorized_from: Option<NodeHandle<IIdentifierNode>>,
+
+    // The parent symbol and scope number of a locally scoped name, e.g. `int __cdecl L(void)'::`2'
+    pub(crate) local_scope: Option<(NodeHandle<ISymbolNode>, u64)>,
+
+    // Whether this names the guard counter of a thread-safe local static
+    pub(crate) is_thread_safe_static_counter: bool,
+}
+
+impl NamedIdentifierNode<'_> {
+    // Thread-safe local statics are guarded by a function local counter named $TSS<index>.
+    #[must_use]
+    pub(crate) fn thread_safe_static_index(&self) -> Option<&str> {
+        self.name
+            .strip_prefix("$TSS")
+            .filter(|x| !x.is_empty() && x.bytes().all(|x| x.is_ascii_digit()))
+    }
 }
 
 impl WriteableNode for NamedIdentifierNode<'_> {
     fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        write!(ob, "{}", self.name)?;
+        if let Some(identifier) = self.memorized_from {
+            return identifier.resolve(cache).output(cache, ob, flags);
+        }
+
+        if let Some((scope, number)) = self.local_scope {
+            write!(ob, "`")?;
+            scope.resolve(cache).output(cache, ob, flags)?;
+            let separator = flags.nested_name_separator();
+            if flags.build_stable_names() {
+                write!(ob, "'{separator}`#'")?;
+            } else if flags.numeric_scope_from_zero() {
+                // the mangled number is already offset by one, e.g. ?0 is 1
+                write!(ob, "'{separator}`{}'", number.saturating_sub(1))?;
+            } else {
+                write!(ob, "'{separator}`{number}'")?;
+            }
+            return Ok(());
+        }
+
+        // Lambdas are numbered by the compiler, which isn't stable across builds.
+        let is_lambda = self
+            .name
+            .strip_prefix("<lambda_")
+            .and_then(|x| x.strip_suffix('>'))
+            .is_some_and(|x| !x.is_empty() && x.bytes().all(|x| x.is_ascii_hexdigit()));
+        if self.template_params.is_generic {
+            write!(ob, "[generic] ")?;
+        }
+        let tss_index = self
+            .thread_safe_static_index()
+            .filter(|_| self.is_thread_safe_static_counter && !flags.llvm_compat());
+        if is_lambda && flags.stable_lambda_numbers() {
+            write!(ob, "<lambda>")?;
+        } else if let Some(index) = tss_index {
+            write!(ob, "`thread-safe static init counter'")?;
+            if index != "0" {
+                write!(ob, "{{{index}}}")?;
+            }
+        } else {
+            write!(ob, "{}", self.name)?;
+        }
         self.template_params.output(cache, ob, flags)
     }
 }
@@ -930,10 +1199,11 @@ pub(crate) struct RttiBaseClassDescriptorNode {
 }
 
 impl WriteableNode for RttiBaseClassDescriptorNode {
-    fn output(&self, _: &NodeCache, ob: &mut dyn Writer, _: OutputFlags) -> Result<()> {
+    fn output(&self, _: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
+        let (open, close) = flags.rtti_quotes();
         write!(
             ob,
-            "`RTTI Base Class Descriptor at ({}, {}, {}, {})'",
+            "{open}RTTI Base Class Descriptor at ({}, {}, {}, {}){close}",
             self.nv_offset, self.vbptr_offset, self.vbtable_offset, self.flags
         )?;
         Ok(())
@@ -1015,10 +1285,110 @@ impl QualifiedNameNode {
 
 impl WriteableNode for QualifiedNameNode {
     fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        self.components
-            .resolve(cache)
-            .do_output(cache, ob, flags, "::")
+        let components = self.components.resolve(cache);
+        let separator = flags.nested_name_separator();
+        let identifier = |node: NodeHandle<INode>| {
+            let node: Option<NodeHandle<IIdentifierNode>> = node.downcast(cache);
+            node.map(|x| x.resolve(cache))
+        };
+        let is_std = |node| match identifier(node) {
+            Some(IdentifierNode::NamedIdentifier(x)) => {
+                x.name == "std" && x.template_params.is_none()
+            }
+            _ => false,
+        };
+        match components.nodes {
+            [first, class, rest @ ..] if flags.abbreviate_std() && is_std(*first) => {
+                write!(ob, "std{separator}")?;
+                output_std_class(*class, cache, ob, flags)?;
+                for &node in rest {
+                    write!(ob, "{separator}")?;
+                    match identifier(node) {
+                        // the class name of a structor is the same as its parent's
+                        Some(IdentifierNode::StructorIdentifier(x)) => {
+                            if x.is_destructor {
+                                write!(ob, "~")?;
+                            }
+                            if let Some(class) = x.class {
+                                output_std_class(class.into(), cache, ob, flags)?;
+                            }
+                            x.template_params.output(cache, ob, flags)?;
+                        }
+                        _ => node.resolve(cache).output(cache, ob, flags)?,
+                    }
+                }
+                Ok(())
+            }
+            _ => components.do_output(cache, ob, flags, separator),
+        }
+    }
+}
+
+// Outputs a class nested directly within the `std` namespace, abbreviating it if its trailing
+// template arguments are all defaulted.
+fn output_std_class(
+    class: NodeHandle<INode>,
+    cache: &NodeCache,
+    ob: &mut dyn Writer,
+    flags: OutputFlags,
+) -> Result<()> {
+    let node = class.resolve(cache);
+    let identifier: Option<NodeHandle<IIdentifierNode>> = class.downcast(cache);
+    let Some(IdentifierNode::NamedIdentifier(identifier)) = identifier.map(|x| x.resolve(cache))
+    else {
+        return node.output(cache, ob, flags);
+    };
+    let Some(params) = identifier.template_params.map(|x| x.resolve(cache)) else {
+        return node.output(cache, ob, flags);
+    };
+
+    // arguments are compared by their canonical rendering
+    let args = params
+        .nodes
+        .iter()
+        .map(|x| {
+            let mut ob = BufWriter::new(Vec::new());
+            x.resolve(cache)
+                .output(cache, &mut ob, OutputFlags::empty())?;
+            Ok(String::from_utf8(ob.into_bytes())?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let char_traits = |x: &str| format!("struct std::char_traits<{x}>");
+    let allocator = |x: &str| format!("class std::allocator<{x}>");
+    let string_prefix = |x: &str| match x {
+        "char" => Some(""),
+        "wchar_t" => Some("w"),
+        "char8_t" => Some("u8"),
+        "char16_t" => Some("u16"),
+        "char32_t" => Some("u32"),
+        _ => None,
+    };
+
+    match (identifier.name, args.as_slice()) {
+        ("basic_string", [ch, traits, alloc])
+            if *traits == char_traits(ch) && *alloc == allocator(ch) =>
+        {
+            if let Some(prefix) = string_prefix(ch) {
+                return write!(ob, "{prefix}string");
+            }
+        }
+        ("basic_string_view", [ch, traits]) if *traits == char_traits(ch) => {
+            if let Some(prefix) = string_prefix(ch) {
+                return write!(ob, "{prefix}string_view");
+            }
+        }
+        ("vector" | "deque" | "list" | "forward_list", [elem, alloc])
+            if *alloc == allocator(elem) =>
+        {
+            write!(ob, "{}", identifier.name)?;
+            let args = NodeArrayNode {
+                nodes: &params.nodes[..1],
+            };
+            return output_template_arguments(&args, cache, ob, flags);
+        }
+        _ => (),
     }
+    node.output(cache, ob, flags)
 }
 
 #[derive(Clone, Default)]
@@ -1064,9 +1434,13 @@ pub(crate) struct IntegerLiteralNode {
 }
 
 impl WriteableNode for IntegerLiteralNode {
-    fn output(&self, _: &NodeCache, ob: &mut dyn Writer, _: OutputFlags) -> Result<()> {
+    fn output(&self, _: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
         let sign = if self.is_negative { "-" } else { "" };
-        write!(ob, "{sign}{}", self.value)?;
+        if flags.canonical_integer_literals() && self.value > 0xFF {
+            write!(ob, "{sign}0x{:X}", self.value)?;
+        } else {
+            write!(ob, "{sign}{}", self.value)?;
+        }
         Ok(())
     }
 }
@@ -1082,10 +1456,21 @@ impl WriteableNode for Md5SymbolNode {
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct PassThroughSymbolNode {
+    pub(crate) name: NodeHandle<QualifiedName>,
+}
+
+impl WriteableNode for PassThroughSymbolNode {
+    fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
+        self.name.resolve(cache).output(cache, ob, flags)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub(crate) struct SpecialTableSymbolNode {
     pub(crate) name: NodeHandle<QualifiedName>,
-    pub(crate) target_name: Option<NodeHandle<QualifiedName>>,
+    pub(crate) target_names: Option<NodeHandle<NodeArray>>,
     pub(crate) quals: Qualifiers,
 }
 
@@ -1095,9 +1480,9 @@ impl WriteableNode for SpecialTableSymbolNode {
             self.quals.output(ob, flags, false, true)?;
         }
         self.name.resolve(cache).output(cache, ob, flags)?;
-        if let Some(target_name) = self.target_name.map(|x| x.resolve(cache)) {
+        if let Some(target_names) = self.target_names.map(|x| x.resolve(cache)) {
             write!(ob, "{{for `")?;
-            target_name.output(cache, ob, flags)?;
+            target_names.do_output(cache, ob, flags, "'s `")?;
             write!(ob, "'}}")?;
         }
         Ok(())
@@ -1143,6 +1528,13 @@ impl WriteableNode for EncodedStringLiteralNode<'_> {
 pub(crate) enum VariableSymbolName {
     Qualified(NodeHandle<QualifiedName>),
     TypeDescriptor,
+
+    // The mangled type of a type descriptor, without the descriptor itself.
+    Type,
+
+    ThrowInfo,
+
+    CatchableTypeArray,
 }
 
 impl From<NodeHandle<QualifiedName>> for VariableSymbolName {
@@ -1156,6 +1548,8 @@ pub(crate) struct VariableSymbolNode {
     pub(crate) name: Option<VariableSymbolName>,
     pub(crate) sc: Option<StorageClass>,
     pub(crate) r#type: Option<NodeHandle<ITypeNode>>,
+    // the spare bytes some producers reserve after an RTTI type descriptor's name
+    pub(crate) extra_bytes: Option<u64>,
 }
 
 impl VariableSymbolNode {
@@ -1174,6 +1568,7 @@ impl VariableSymbolNode {
             name: Some(name.into()),
             sc: None,
             r#type: Some(r#type),
+            extra_bytes: None,
         })
     }
 }
@@ -1204,13 +1599,18 @@ impl WriteableNode for VariableSymbolNode {
                 let r#type = (!flags.no_variable_type() && !flags.name_only())
                     .then(|| self.r#type.map(|x| x.resolve(cache)))
                     .flatten();
+                let type_flags = if flags.no_cc_on_data() {
+                    flags | OutputFlags::NO_CALLING_CONVENTION
+                } else {
+                    flags
+                };
                 if let Some(r#type) = r#type {
-                    r#type.output_pre(cache, ob, flags)?;
+                    r#type.output_pre(cache, ob, type_flags)?;
                     super::output_space_if_necessary(ob)?;
                 }
                 name.resolve(cache).output(cache, ob, flags)?;
                 if let Some(r#type) = r#type {
-                    r#type.output_post(cache, ob, flags)?;
+                    r#type.output_post(cache, ob, type_flags)?;
                 }
             }
             VariableSymbolName::TypeDescriptor => {
@@ -1218,9 +1618,34 @@ impl WriteableNode for VariableSymbolNode {
                     r#type.resolve(cache).output(cache, ob, flags)?;
                 }
                 if !flags.name_only() {
-                    super::output_space_if_necessary(ob)?;
-                    write!(ob, "`RTTI Type Descriptor Name'")?;
+                    // Function types end with their parameter list, which would otherwise run into the name.
+                    if ob.last_char() == Some(')') {
+                        write!(ob, " ")?;
+                    } else {
+                        super::output_space_if_necessary(ob)?;
+                    }
+                    let (open, close) = flags.rtti_quotes();
+                    write!(ob, "{open}RTTI Type Descriptor Name{close}")?;
+                }
+            }
+            VariableSymbolName::Type => {
+                if let Some(r#type) = self.r#type {
+                    r#type.resolve(cache).output(cache, ob, flags)?;
+                }
+            }
+            VariableSymbolName::ThrowInfo => {
+                write!(ob, "`throw info for ")?;
+                if let Some(r#type) = self.r#type {
+                    r#type.resolve(cache).output(cache, ob, flags)?;
                 }
+                write!(ob, "'")?;
+            }
+            VariableSymbolName::CatchableTypeArray => {
+                write!(ob, "`catchable type array for ")?;
+                if let Some(r#type) = self.r#type {
+                    r#type.resolve(cache).output(cache, ob, flags)?;
+                }
+                write!(ob, "'")?;
             }
         }
 
@@ -1234,13 +1659,92 @@ pub(crate) struct FunctionSymbolNode {
     pub(crate) signature: NodeHandle<ISignatureNode>,
 }
 
-impl WriteableNode for FunctionSymbolNode {
-    fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
-        self.signature.resolve(cache).output_pre(cache, ob, flags)?;
+impl FunctionSymbolNode {
+    #[must_use]
+    fn is_conversion_operator(&self, cache: &NodeCache) -> bool {
+        self.name
+            .and_then(|x| x.resolve(cache).get_unqualified_identifier(cache))
+            .is_some_and(|x| {
+                matches!(
+                    x.resolve(cache),
+                    IdentifierNode::ConversionOperatorIdentifier(_)
+                )
+            })
+    }
+
+    /// Outputs this symbol as the primary declaration, with each of its parameters on their own line.
+    pub(crate) fn output_params_on_lines(
+        &self,
+        cache: &NodeCache,
+        ob: &mut dyn Writer,
+        flags: OutputFlags,
+    ) -> Result<()> {
+        self.do_output(cache, ob, flags, true)
+    }
+
+    fn do_output(
+        &self,
+        cache: &NodeCache,
+        ob: &mut dyn Writer,
+        flags: OutputFlags,
+        one_per_line: bool,
+    ) -> Result<()> {
+        // the return type of a conversion operator is always its target type
+        let signature_flags =
+            if flags.no_conversion_operator_target() && self.is_conversion_operator(cache) {
+                flags | OutputFlags::NO_RETURN_TYPE
+            } else {
+                flags
+            };
+
+        self.signature
+            .resolve(cache)
+            .output_pre(cache, ob, signature_flags)?;
         super::output_space_if_necessary(ob)?;
         if let Some(name) = self.name {
             name.resolve(cache).output(cache, ob, flags)?;
         }
-        self.signature.resolve(cache).output_post(cache, ob, flags)
+
+        let signature = self.signature.resolve(cache);
+        let has_this_parameter = match signature {
+            SignatureNode::FunctionSignature(func) => func.has_this_parameter(),
+            SignatureNode::ThunkSignature(thunk) => thunk.has_this_parameter(),
+        };
+        let this_class = self
+            .name
+            .filter(|_| flags.render_this_parameter() && has_this_parameter)
+            .and_then(|x| {
+                x.resolve(cache)
+                    .components
+                    .resolve(cache)
+                    .nodes
+                    .split_last()
+            })
+            .filter(|(_, scope)| !scope.is_empty())
+            .map(|(_, nodes)| NodeArrayNode { nodes });
+        match signature {
+            SignatureNode::FunctionSignature(func) => func.do_output_post(
+                cache,
+                ob,
+                signature_flags,
+                false,
+                one_per_line,
+                this_class.as_ref(),
+            ),
+            SignatureNode::ThunkSignature(thunk) => thunk.do_output_post(
+                cache,
+                ob,
+                signature_flags,
+                false,
+                one_per_line,
+                this_class.as_ref(),
+            ),
+        }
+    }
+}
+
+impl WriteableNode for FunctionSymbolNode {
+    fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
+        self.do_output(cache, ob, flags, false)
     }
 }
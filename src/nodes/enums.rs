@@ -381,6 +381,11 @@ impl FuncClass {
         self.contains(Self::FC_Virtual)
     }
 
+    #[must_use]
+    pub(crate) fn is_far(self) -> bool {
+        self.contains(Self::FC_Far)
+    }
+
     #[must_use]
     pub(crate) fn is_extern_c(self) -> bool {
         self.contains(Self::FC_ExternC)
@@ -36,6 +36,7 @@ use crate::{
         Md5SymbolNode,
         NamedIdentifierNode,
         NodeArrayNode,
+        PassThroughSymbolNode,
         PointerTypeNode,
         PrimitiveTypeNode,
         QualifiedNameNode,
@@ -46,6 +47,7 @@ use crate::{
         StructorIdentifierNode,
         TagTypeNode,
         TemplateParameterReferenceNode,
+        TemplateParameters,
         ThunkSignatureNode,
         VariableSymbolName,
         VariableSymbolNode,
@@ -156,6 +158,7 @@ impl_upcast!(&'storage IntegerLiteralNode => NodeConst::IntegerLiteral);
 
 impl_upcast!(SymbolNodeConst<'storage, 'alloc> => NodeConst::Symbol);
 impl_upcast!(&'storage Md5SymbolNode => NodeConst::Symbol);
+impl_upcast!(&'storage PassThroughSymbolNode => NodeConst::Symbol);
 impl_upcast!(&'storage SpecialTableSymbolNode => NodeConst::Symbol);
 impl_upcast!(&'storage LocalStaticGuardVariableNode => NodeConst::Symbol);
 impl_upcast!(&'storage EncodedStringLiteralNode<'alloc> => NodeConst::Symbol);
@@ -260,6 +263,7 @@ impl_upcast!(&'storage mut IntegerLiteralNode => NodeMut::IntegerLiteral);
 
 impl_upcast!(SymbolNodeMut<'storage, 'alloc> => NodeMut::Symbol);
 impl_upcast!(&'storage mut Md5SymbolNode => NodeMut::Symbol);
+impl_upcast!(&'storage mut PassThroughSymbolNode => NodeMut::Symbol);
 impl_upcast!(&'storage mut SpecialTableSymbolNode => NodeMut::Symbol);
 impl_upcast!(&'storage mut LocalStaticGuardVariableNode => NodeMut::Symbol);
 impl_upcast!(&'storage mut EncodedStringLiteralNode<'alloc> => NodeMut::Symbol);
@@ -640,6 +644,23 @@ pub(super) type IdentifierNodeConst<'storage, 'alloc> = IdentifierNode<
     &'storage RttiBaseClassDescriptorNode,
 >;
 
+impl<'storage, 'alloc: 'storage> IdentifierNodeConst<'storage, 'alloc> {
+    #[must_use]
+    pub(crate) fn get_template_params(&self) -> TemplateParameters {
+        match self {
+            Self::VcallThunkIdentifier(x) => x.template_params,
+            Self::DynamicStructorIdentifier(x) => x.template_params,
+            Self::NamedIdentifier(x) => x.template_params,
+            Self::IntrinsicFunctionIdentifier(x) => x.template_params,
+            Self::LiteralOperatorIdentifier(x) => x.template_params,
+            Self::LocalStaticGuardIdentifier(x) => x.template_params,
+            Self::ConversionOperatorIdentifier(x) => x.template_params,
+            Self::StructorIdentifier(x) => x.template_params,
+            Self::RttiBaseClassDescriptor(x) => x.template_params,
+        }
+    }
+}
+
 impl<'storage, 'alloc: 'storage> WriteableNode for IdentifierNodeConst<'storage, 'alloc> {
     fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
         match self {
@@ -689,7 +710,11 @@ pub(super) type IdentifierNodeMut<'storage, 'alloc> = IdentifierNode<
 >;
 
 impl<'storage, 'alloc: 'storage> IdentifierNodeMut<'storage, 'alloc> {
-    pub(crate) fn set_template_params(&mut self, template_params: NodeHandle<NodeArray>) {
+    pub(crate) fn set_template_params(
+        &mut self,
+        template_params: NodeHandle<NodeArray>,
+        is_generic: bool,
+    ) {
         let params = match self {
             Self::VcallThunkIdentifier(x) => &mut x.template_params,
             Self::DynamicStructorIdentifier(x) => &mut x.template_params,
@@ -701,7 +726,8 @@ impl<'storage, 'alloc: 'storage> IdentifierNodeMut<'storage, 'alloc> {
             Self::StructorIdentifier(x) => &mut x.template_params,
             Self::RttiBaseClassDescriptor(x) => &mut x.template_params,
         };
-        params.0 = Some(template_params);
+        params.params = Some(template_params);
+        params.is_generic = is_generic;
     }
 }
 
@@ -728,6 +754,7 @@ impl_downcast!(IdentifierNodeMut::RttiBaseClassDescriptor => &'storage mut RttiB
 #[derive(Clone, Copy)]
 pub(crate) enum SymbolNode<
     Md5SymbolT,
+    PassThroughSymbolT,
     SpecialTableSymbolT,
     LocalStaticGuardVariableT,
     EncodedStringLiteralT,
@@ -735,6 +762,7 @@ pub(crate) enum SymbolNode<
     FunctionSymbolT,
 > {
     Md5Symbol(Md5SymbolT),
+    PassThroughSymbol(PassThroughSymbolT),
     SpecialTableSymbol(SpecialTableSymbolT),
     LocalStaticGuardVariable(LocalStaticGuardVariableT),
     EncodedStringLiteral(EncodedStringLiteralT),
@@ -744,6 +772,7 @@ pub(crate) enum SymbolNode<
 
 pub(super) type SymbolNodeConst<'storage, 'alloc> = SymbolNode<
     &'storage Md5SymbolNode,
+    &'storage PassThroughSymbolNode,
     &'storage SpecialTableSymbolNode,
     &'storage LocalStaticGuardVariableNode,
     &'storage EncodedStringLiteralNode<'alloc>,
@@ -756,6 +785,7 @@ impl<'storage, 'alloc: 'storage> SymbolNodeConst<'storage, 'alloc> {
     pub(crate) fn get_name(&self) -> Option<NodeHandle<QualifiedName>> {
         match self {
             Self::Md5Symbol(x) => Some(x.name),
+            Self::PassThroughSymbol(x) => Some(x.name),
             Self::SpecialTableSymbol(x) => Some(x.name),
             Self::LocalStaticGuardVariable(x) => Some(x.name),
             Self::EncodedStringLiteral(x) => x.name,
@@ -775,6 +805,7 @@ impl<'storage, 'alloc: 'storage> WriteableNode for SymbolNodeConst<'storage, 'al
     fn output(&self, cache: &NodeCache, ob: &mut dyn Writer, flags: OutputFlags) -> Result<()> {
         match self {
             Self::Md5Symbol(x) => x.output(cache, ob, flags),
+            Self::PassThroughSymbol(x) => x.output(cache, ob, flags),
             Self::SpecialTableSymbol(x) => x.output(cache, ob, flags),
             Self::LocalStaticGuardVariable(x) => x.output(cache, ob, flags),
             Self::EncodedStringLiteral(x) => x.output(cache, ob, flags),
@@ -785,6 +816,7 @@ impl<'storage, 'alloc: 'storage> WriteableNode for SymbolNodeConst<'storage, 'al
 }
 
 impl_upcast!(&'storage Md5SymbolNode => SymbolNodeConst::Md5Symbol);
+impl_upcast!(&'storage PassThroughSymbolNode => SymbolNodeConst::PassThroughSymbol);
 impl_upcast!(&'storage SpecialTableSymbolNode => SymbolNodeConst::SpecialTableSymbol);
 impl_upcast!(&'storage LocalStaticGuardVariableNode => SymbolNodeConst::LocalStaticGuardVariable);
 impl_upcast!(&'storage EncodedStringLiteralNode<'alloc> => SymbolNodeConst::EncodedStringLiteral);
@@ -792,6 +824,7 @@ impl_upcast!(&'storage VariableSymbolNode => SymbolNodeConst::VariableSymbol);
 impl_upcast!(&'storage FunctionSymbolNode => SymbolNodeConst::FunctionSymbol);
 
 impl_downcast!(SymbolNodeConst::Md5Symbol => &'storage Md5SymbolNode);
+impl_downcast!(SymbolNodeConst::PassThroughSymbol => &'storage PassThroughSymbolNode);
 impl_downcast!(SymbolNodeConst::SpecialTableSymbol => &'storage SpecialTableSymbolNode);
 impl_downcast!(SymbolNodeConst::LocalStaticGuardVariable => &'storage LocalStaticGuardVariableNode);
 impl_downcast!(SymbolNodeConst::EncodedStringLiteral => &'storage EncodedStringLiteralNode<'alloc>);
@@ -800,6 +833,7 @@ impl_downcast!(SymbolNodeConst::FunctionSymbol => &'storage FunctionSymbolNode);
 
 pub(super) type SymbolNodeMut<'storage, 'alloc> = SymbolNode<
     &'storage mut Md5SymbolNode,
+    &'storage mut PassThroughSymbolNode,
     &'storage mut SpecialTableSymbolNode,
     &'storage mut LocalStaticGuardVariableNode,
     &'storage mut EncodedStringLiteralNode<'alloc>,
@@ -811,6 +845,7 @@ impl<'storage, 'alloc: 'storage> SymbolNodeMut<'storage, 'alloc> {
     pub(crate) fn set_name(&mut self, name: NodeHandle<QualifiedName>) {
         match self {
             Self::Md5Symbol(x) => x.name = name,
+            Self::PassThroughSymbol(x) => x.name = name,
             Self::SpecialTableSymbol(x) => x.name = name,
             Self::LocalStaticGuardVariable(x) => x.name = name,
             Self::EncodedStringLiteral(x) => x.name = Some(name),
@@ -821,6 +856,7 @@ impl<'storage, 'alloc: 'storage> SymbolNodeMut<'storage, 'alloc> {
 }
 
 impl_upcast!(&'storage mut Md5SymbolNode => SymbolNodeMut::Md5Symbol);
+impl_upcast!(&'storage mut PassThroughSymbolNode => SymbolNodeMut::PassThroughSymbol);
 impl_upcast!(&'storage mut SpecialTableSymbolNode => SymbolNodeMut::SpecialTableSymbol);
 impl_upcast!(&'storage mut LocalStaticGuardVariableNode => SymbolNodeMut::LocalStaticGuardVariable);
 impl_upcast!(&'storage mut EncodedStringLiteralNode<'alloc> => SymbolNodeMut::EncodedStringLiteral);
@@ -828,6 +864,7 @@ impl_upcast!(&'storage mut VariableSymbolNode => SymbolNodeMut::VariableSymbol);
 impl_upcast!(&'storage mut FunctionSymbolNode => SymbolNodeMut::FunctionSymbol);
 
 impl_downcast!(SymbolNodeMut::Md5Symbol => &'storage mut Md5SymbolNode);
+impl_downcast!(SymbolNodeMut::PassThroughSymbol => &'storage mut PassThroughSymbolNode);
 impl_downcast!(SymbolNodeMut::SpecialTableSymbol => &'storage mut SpecialTableSymbolNode);
 impl_downcast!(SymbolNodeMut::LocalStaticGuardVariable => &'storage mut LocalStaticGuardVariableNode);
 impl_downcast!(SymbolNodeMut::EncodedStringLiteral => &'storage mut EncodedStringLiteralNode<'alloc>);
@@ -41,6 +41,8 @@ mod intermediate;
 // ├── TemplateParameterReferenceNode
 // ├── IntegerLiteralNode
 // └── SymbolNode
+//     ├── Md5SymbolNode
+//     ├── PassThroughSymbolNode
 //     ├── SpecialTableSymbolNode
 //     ├── LocalStaticGuardVariableNode
 //     ├── EncodedStringLiteralNode
@@ -69,6 +71,7 @@ pub(crate) use derived::{
     Md5SymbolNode,
     NamedIdentifierNode,
     NodeArrayNode,
+    PassThroughSymbolNode,
     PointerTypeNode,
     PrimitiveTypeNode,
     QualifiedNameNode,
@@ -172,6 +175,8 @@ assert_trivial_drop!(QualifiedNameNode);
 assert_trivial_drop!(TemplateParameterReferenceNode);
 assert_trivial_drop!(IntegerLiteralNode);
 
+assert_trivial_drop!(Md5SymbolNode);
+assert_trivial_drop!(PassThroughSymbolNode);
 assert_trivial_drop!(SpecialTableSymbolNode);
 assert_trivial_drop!(LocalStaticGuardVariableNode);
 assert_trivial_drop!(EncodedStringLiteralNode);
@@ -0,0 +1,91 @@
+// Copyright 2024 Ryan McKenzie
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    demangler::Demangler,
+    Flags,
+    Result,
+};
+use bumpalo::Bump;
+use std::io;
+
+mod private {
+    use std::io;
+
+    pub trait Sealed {}
+
+    impl Sealed for String {}
+    impl Sealed for &mut String {}
+    impl Sealed for &mut Vec<u8> {}
+    impl Sealed for &mut dyn io::Write {}
+}
+
+/// A destination for demangled output. See [`demangle_to`](crate::demangle_to) for more info.
+///
+/// This trait is sealed, and can not be implemented outside of this crate.
+pub trait DemangleSink: private::Sealed {
+    /// The value returned from [`demangle_to`](crate::demangle_to) on success.
+    type Output;
+
+    #[doc(hidden)]
+    fn demangle(self, mangled_name: &str, flags: Flags) -> Result<Self::Output>;
+}
+
+/// Demangles into a fresh `String`, which is returned on success.
+impl DemangleSink for String {
+    type Output = String;
+
+    fn demangle(mut self, mangled_name: &str, flags: Flags) -> Result<Self::Output> {
+        (&mut self).demangle(mangled_name, flags)?;
+        Ok(self)
+    }
+}
+
+/// Clears the `String` and then demangles into it, reusing its allocation.
+/// The `String` is left empty on failure.
+impl DemangleSink for &mut String {
+    type Output = ();
+
+    fn demangle(self, mangled_name: &str, flags: Flags) -> Result<Self::Output> {
+        let alloc = Bump::default();
+        let d = Demangler::new(mangled_name, flags, &alloc);
+        self.clear();
+        d.parse_into(self)
+    }
+}
+
+/// Clears the `Vec` and then demangles into it as utf-8, reusing its allocation.
+/// The `Vec` is left empty on failure.
+impl DemangleSink for &mut Vec<u8> {
+    type Output = ();
+
+    fn demangle(self, mangled_name: &str, flags: Flags) -> Result<Self::Output> {
+        let alloc = Bump::default();
+        let d = Demangler::new(mangled_name, flags, &alloc);
+        self.clear();
+        d.parse_into_bytes(self)
+    }
+}
+
+/// Writes the demangled output directly into the stream, without any intermediate buffering.
+/// Partial output may be written on failure.
+impl DemangleSink for &mut dyn io::Write {
+    type Output = ();
+
+    fn demangle(self, mangled_name: &str, flags: Flags) -> Result<Self::Output> {
+        let alloc = Bump::default();
+        let d = Demangler::new(mangled_name, flags, &alloc);
+        d.parse_into_io(self)
+    }
+}
@@ -12,8 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::Flags;
+use crate::{
+    Access,
+    ComparisonCategory,
+    Flags,
+    FunctionAttributes,
+    Producer,
+    RttiKind,
+    StructorKind,
+    SymbolKind,
+    VariableNameKind,
+};
 use memchr::memmem;
+use std::{
+    fmt,
+    io,
+};
 
 fn do_test(mangled_name: &str, demangled_name: &str, partial_match: bool, flags: Flags) {
     let result = crate::demangle(mangled_name.into(), flags);
@@ -37,6 +51,10 @@ fn do_test(mangled_name: &str, demangled_name: &str, partial_match: bool, flags:
             "'{mangled_name}' <-- mangled string\n{err:?} <-- error",
         ),
     }
+    assert!(
+        crate::validate(mangled_name, flags).is_ok(),
+        "'{mangled_name}' <-- mangled string\n{flags:?} <-- flags\n'Ok(())' <-- expected validation",
+    );
 }
 
 fn test(mangled_name: &str, demangled_name: &str) {
@@ -56,11 +74,20 @@ fn test_invalid_manglings() {
                 );
             }
         }
+        assert!(
+            crate::validate(mangled_name, Flags::default()).is_err(),
+            "'{mangled_name}' <-- mangled string\n'Err(_)' <-- expected validation",
+        );
     };
 
     test_invalid("?ff@@$$J0YAXAU?$AS_@$0A@PEAU?$AS_@$0A@H@__clang@@@__clang@@@Z");
     test_invalid("?f0@@YAXPEU?$AS_@$00$$CAD@__clang@@@Z");
     test_invalid("?@@8");
+    test_invalid("??__K@@YAHO@Z");
+    test_invalid("??__K_deg");
+    test_invalid("??__K?_deg@@YAHO@Z");
+    test_invalid("??__K2km@@YAHO@Z");
+    test_invalid("??__K_k.m@@YAHO@Z");
     test_invalid("??");
     test_invalid("??0@");
     test_invalid("? @@   YC@");
@@ -101,6 +128,9 @@ fn test_invalid_manglings() {
     test_invalid("??_C@_");
     test_invalid("??_C@_3");
     test_invalid("??_C@_01");
+    test_invalid("??_C@_10CNPNBAHC@?$AA@");
+    test_invalid("??_C@_10CNPNBAHC@@");
+    test_invalid("??_C@_00CNPNBAHC@");
     test_invalid("??_C@_0101234567@");
     test_invalid("??_C@_0101234567@?");
     test_invalid("??_C@_0101234567@?$");
@@ -133,6 +163,8 @@ fn test_invalid_manglings() {
     test_invalid("??_R2foo@@1");
     test_invalid("??_A");
     test_invalid("??_P");
+    test_invalid("??_P?0klass@@QAE@XZ");
+    test_invalid("??_P?Hklass@@3HA");
     test_invalid(".?AUBase@@@8");
 }
 
@@ -349,6 +381,17 @@ fn test_arg_qualifiers() {
         "?foo_qay144cbh@@YAX$$QEAY144$$CBH@Z",
         "void __cdecl foo_qay144cbh(int const (&&)[5][5])",
     );
+    test(
+        "?foo_aay144cch@@YAXAAY144$$CCH@Z",
+        "void __cdecl foo_aay144cch(int volatile (&)[5][5])",
+    );
+    test(
+        "?foo_aay144cdh@@YAXAEAY144$$CDH@Z",
+        "void __cdecl foo_aay144cdh(int const volatile (&)[5][5])",
+    );
+    test("?x@@3PEAY02$$CCHEA", "int volatile (*x)[3]");
+    test("?x@@3PEAY02$$CDHEA", "int const volatile (*x)[3]");
+    test("?x@@3PEAY02$$CCUS@@EA", "struct S volatile (*x)[3]");
     test(
         "?foo_p6ahxz@@YAXP6AHXZ@Z",
         "void __cdecl foo_p6ahxz(int (__cdecl *)(void))",
@@ -662,6 +705,18 @@ fn test_back_references() {
         "??$?HH@S@@QEAAAEAU0@H@Z",
         "struct S & __cdecl S::operator+<int>(int)",
     );
+    test(
+        "??$?HH@@YA?AUS@@AEBU0@H@Z",
+        "struct S __cdecl operator+<int>(struct S const &, int)",
+    );
+    test(
+        "??$?8H@@YA_NAEBH0@Z",
+        "bool __cdecl operator==<int>(int const &, int const &)",
+    );
+    test(
+        "??$?6H@std@@YAAEAV?$basic_ostream@DU?$char_traits@D@std@@@0@AEAV10@H@Z",
+        "class std::basic_ostream<char, struct std::char_traits<char>> & __cdecl std::operator<<<int>(class std::basic_ostream<char, struct std::char_traits<char>> &, int)",
+    );
     test(
         "?foo_abbb@@YAXV?$A@V?$B@D@@V1@V1@@@@Z",
         "void __cdecl foo_abbb(class A<class B<char>, class B<char>, class B<char>>)",
@@ -866,7 +921,12 @@ fn test_basic() {
     test("??Oklass@@QEAAHH@Z", "int __cdecl klass::operator>(int)");
     test("??Pklass@@QEAAHH@Z", "int __cdecl klass::operator>=(int)");
     test("??Qklass@@QEAAHH@Z", "int __cdecl klass::operator,(int)");
+    test(
+        "??Qklass@@QEAAHHH@Z",
+        "int __cdecl klass::operator,(int, int)",
+    );
     test("??Rklass@@QEAAHH@Z", "int __cdecl klass::operator()(int)");
+    test("??Rklass@@QEAAHXZ", "int __cdecl klass::operator()(void)");
     test("??Sklass@@QEAAHXZ", "int __cdecl klass::operator~(void)");
     test("??Tklass@@QEAAHH@Z", "int __cdecl klass::operator^(int)");
     test("??Uklass@@QEAAHH@Z", "int __cdecl klass::operator|(int)");
@@ -908,6 +968,19 @@ fn test_basic() {
         "?A@?A0x43583946@@3VB@@B",
         "class B const `anonymous namespace'::A",
     );
+    test("?x@?A@@3HA", "int `anonymous namespace'::x");
+    test(
+        "?f@?A@@YAXVB@1@@Z",
+        "void __cdecl `anonymous namespace'::f(class `anonymous namespace'::B)",
+    );
+    test(
+        "?f@?A@x@@YAXVB@12@@Z",
+        "void __cdecl x::`anonymous namespace'::f(class x::`anonymous namespace'::B)",
+    );
+    test(
+        "?f@?A@x@@YAXVB@1@V3@@Z",
+        "void __cdecl x::`anonymous namespace'::f(class `anonymous namespace'::B, class B)",
+    );
 }
 
 #[test]
@@ -1010,6 +1083,18 @@ fn test_cxx11() {
         "?i@FTypeWithQuals@@3U?$S@$$A8@@IHAAHXZ@1@A",
         "struct FTypeWithQuals::S<int __cdecl(void) __restrict &&> FTypeWithQuals::i",
     );
+    test(
+        "?k@FTypeWithQuals@@3U?$S@$$A8@@IGAAHX_E@1@A",
+        "struct FTypeWithQuals::S<int __cdecl(void) __restrict & noexcept> FTypeWithQuals::k",
+    );
+    test(
+        "?l@FTypeWithQuals@@3U?$S@$$A8@@IHDAHX_E@1@A",
+        "struct FTypeWithQuals::S<int __cdecl(void) const volatile __restrict && noexcept> FTypeWithQuals::l",
+    );
+    test(
+        "?m@FTypeWithQuals@@3U?$S@$$A8@@IAAHX_E@1@A",
+        "struct FTypeWithQuals::S<int __cdecl(void) __restrict noexcept> FTypeWithQuals::m",
+    );
     test(
         "?j@FTypeWithQuals@@3U?$S@$$A6AHXZ@1@A",
         "struct FTypeWithQuals::S<int __cdecl(void)> FTypeWithQuals::j",
@@ -1050,6 +1135,27 @@ fn test_cxx11() {
         "??__K_deg@@YAHO@Z",
         "int __cdecl operator \"\"_deg(long double)",
     );
+    test(
+        "??__K_deg@0@YAH_K@Z",
+        "int __cdecl _deg::operator \"\"_deg(unsigned __int64)",
+    );
+    test(
+        "??__K_km@units@@YA?AUkm@1@O@Z",
+        "struct units::km __cdecl units::operator \"\"_km(long double)",
+    );
+    test("??__K_@@YAHO@Z", "int __cdecl operator \"\"_(long double)");
+    test(
+        "??__K_km2@@YAHO@Z",
+        "int __cdecl operator \"\"_km2(long double)",
+    );
+    test(
+        "??__K_0@@YAHPEBD@Z",
+        "int __cdecl operator \"\"_0(char const *)",
+    );
+    test(
+        "??__Kif@@YA?AU?$complex@M@std@@O@Z",
+        "struct std::complex<float> __cdecl operator \"\"if(long double)",
+    );
     test(
         "??$templ_fun_with_pack@$S@@YAXXZ",
         "void __cdecl templ_fun_with_pack<>(void)",
@@ -1058,6 +1164,20 @@ fn test_cxx11() {
         "??$func@H$$ZH@@YAHAEBU?$Foo@H@@0@Z",
         "int __cdecl func<int, int>(struct Foo<int> const &, struct Foo<int> const &)",
     );
+    test(
+        "??$f@HH$$ZDD@@YAXXZ",
+        "void __cdecl f<int, int, char, char>(void)",
+    );
+    test(
+        "??$f@H$$ZDN$$ZM@@YAXXZ",
+        "void __cdecl f<int, char, double, float>(void)",
+    );
+    test(
+        "??0?$VariadicClass@H$$ZD_N@@QAE@XZ",
+        "__thiscall VariadicClass<int, char, bool>::VariadicClass<int, char, bool>(void)",
+    );
+    test("??$f@$$V$$ZH@@YAXXZ", "void __cdecl f<int>(void)");
+    test("??$f@H$$Z$$V@@YAXXZ", "void __cdecl f<int>(void)");
     test(
         "??$templ_fun_with_ty_pack@$$$V@@YAXXZ",
         "void __cdecl templ_fun_with_ty_pack<>(void)",
@@ -1070,6 +1190,18 @@ fn test_cxx11() {
         "??$f@$$YAliasA@PR20047@@@PR20047@@YAXXZ",
         "void __cdecl PR20047::f<PR20047::AliasA>(void)",
     );
+    test(
+        "??$f@V?$Outer@$$YAliasA@PR20047@@@@@PR20047@@YAXXZ",
+        "void __cdecl PR20047::f<class Outer<PR20047::AliasA>>(void)",
+    );
+    test(
+        "??$f@$$Y?$AliasB@$$YAliasA@PR20047@@@PR20047@@@PR20047@@YAXXZ",
+        "void __cdecl PR20047::f<PR20047::AliasB<PR20047::AliasA>>(void)",
+    );
+    test(
+        "??$f@$$Y?$Outer@H@@@@YAXXZ",
+        "void __cdecl f<Outer<int>>(void)",
+    );
     test(
         "?f@UnnamedType@@YAXAAU<unnamed-type-TD>@A@1@@Z",
         "void __cdecl UnnamedType::f(struct UnnamedType::A::<unnamed-type-TD> &)",
@@ -1147,6 +1279,16 @@ fn test_cxx14() {
         "<auto> __cdecl TemplateFuncionWithLocalLambda<int>(int)",
     );
     test("??R<lambda_1>@?0???$TemplateFuncionWithLocalLambda@H@@YA?A?<auto>@@H@Z@QBE?A?1@XZ", "<auto> __thiscall `<auto> __cdecl TemplateFuncionWithLocalLambda<int>(int)'::`1'::<lambda_1>::operator()(void) const");
+    // generic lambdas instantiate their call operator, not the closure type
+    test(
+        "??$?RH@<lambda_0>@@QBE?A?<auto>@@XZ",
+        "<auto> __thiscall <lambda_0>::operator()<int>(void) const",
+    );
+    test(
+        "??$?RH@<lambda_0>@?0??f@@YAXXZ@QBE?A?<auto>@@H@Z",
+        "<auto> __thiscall `void __cdecl f(void)'::`1'::<lambda_0>::operator()<int>(int) const",
+    );
+    test("??$?RHN@<lambda_1>@?0???$TemplateFuncionWithLocalLambda@H@@YA?A?<auto>@@H@Z@QBE?A?1@HN@Z", "<auto> __thiscall `<auto> __cdecl TemplateFuncionWithLocalLambda<int>(int)'::`1'::<lambda_1>::operator()<int, double>(int, double) const");
     test("??$WithPMD@$GA@A@?0@@3HA", "int WithPMD<{0, 0, -1}>");
     test(
         "?Zoo@@3U?$Foo@$1??$x@H@@3HA$1?1@3HA@@A",
@@ -1180,6 +1322,62 @@ fn test_cxx17_noexcept() {
         "?ee@?$e@$$A6AXX_E@@EEAAXXZ",
         "private: virtual void __cdecl e<void __cdecl(void) noexcept>::ee(void)",
     );
+
+    // the throw specification is consumed before any following backrefs
+    test(
+        "?f@@YAXV?$C@P6AXVS@@@_EV1@@@@Z",
+        "void __cdecl f(class C<void (__cdecl *)(class S) noexcept, class S>)",
+    );
+    test(
+        "?f@@YAXV?$C@P6AXVS@@@ZV1@@@@Z",
+        "void __cdecl f(class C<void (__cdecl *)(class S), class S>)",
+    );
+    test(
+        "?f@@YAXV?$C@$$A6AXVS@@@_EV1@@@@Z",
+        "void __cdecl f(class C<void __cdecl(class S) noexcept, class S>)",
+    );
+    test(
+        "?f@@YAXV?$C@P6AXVS@@@_EV1@@@0@Z",
+        "void __cdecl f(class C<void (__cdecl *)(class S) noexcept, class S>, class C<void (__cdecl *)(class S) noexcept, class S>)",
+    );
+    test(
+        "?f@@YAXV?$C@P6AXVS@@@_EV1@@@P6AXV1@@_E@Z",
+        "void __cdecl f(class C<void (__cdecl *)(class S) noexcept, class S>, void (__cdecl *)(class C<void (__cdecl *)(class S) noexcept, class S>) noexcept)",
+    );
+
+    // noexcept is always spelled the same way, wherever it appears
+    test("?f@@YAXX_E", "void __cdecl f(void) noexcept");
+    test(
+        "?f@S@@QBEXX_E",
+        "public: void __thiscall S::f(void) const noexcept",
+    );
+    test(
+        "?f@S@@QEGBAXX_E",
+        "public: void __cdecl S::f(void) const & noexcept",
+    );
+    test(
+        "?f@@YAXA6AXX_E@Z",
+        "void __cdecl f(void (__cdecl &)(void) noexcept)",
+    );
+    test(
+        "?f@@YAXP8S@@AEXX_E@Z",
+        "void __cdecl f(void (__thiscall S::*)(void) noexcept)",
+    );
+    test(
+        "?x@@3PAY01P6AXX_EA",
+        "void (__cdecl *(*x)[2])(void) noexcept",
+    );
+    test(
+        "?f@@YAXP6AXP6AXX_E@_E@Z",
+        "void __cdecl f(void (__cdecl *)(void (__cdecl *)(void) noexcept) noexcept)",
+    );
+    do_test("?f@S@@QEGBAXX_E", "S::f", false, Flags::NAME_ONLY);
+    do_test(
+        "?f@S@@QEGBAXX_E",
+        "public: void __cdecl S::f",
+        false,
+        Flags::NO_ARGUMENTS,
+    );
 }
 
 #[test]
@@ -1249,6 +1447,18 @@ fn test_mangle() {
         "?FunArr@@3PAY0BE@P6AHHH@ZA",
         "int (__cdecl *(*FunArr)[20])(int, int)",
     );
+    test(
+        "?FunArr2@@3PAY112P6AHH@ZA",
+        "int (__cdecl *(*FunArr2)[2][3])(int)",
+    );
+    test(
+        "?FunArr3@@3PAY112QAP6AHH@ZA",
+        "int (__cdecl **const (*FunArr3)[2][3])(int)",
+    );
+    test(
+        "?FunArr4@@3PAY112P6APAY01HH@ZA",
+        "int (* (__cdecl *(*FunArr4)[2][3])(int))[2]",
+    );
     test(
         "?j@@3P6GHCE@ZA",
         "int (__stdcall *j)(signed char, unsigned char)",
@@ -1600,6 +1810,26 @@ fn test_nested_scopes() {
         "?M@?BB@??L@@YAHXZ@4HA",
         "int `int __cdecl L(void)'::`17'::M",
     );
+    test(
+        "?M@?PP@??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`255'::M",
+    );
+    test(
+        "?M@?BAA@??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`256'::M",
+    );
+    test(
+        "?M@?BAB@??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`257'::M",
+    );
+    test(
+        "?M@?PPP@??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`4095'::M",
+    );
+    test(
+        "?M@?BAAA@??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`4096'::M",
+    );
     test(
         "?j@?1??L@@YAHXZ@4UJ@@A",
         "struct J `int __cdecl L(void)'::`2'::j",
@@ -1729,6 +1959,14 @@ fn test_operators() {
         "??_DDiamond@@QEAAXXZ",
         "void __cdecl Diamond::`vbase dtor'(void)",
     );
+    test(
+        "??_D?$Diamond@H@@QEAAXXZ",
+        "void __cdecl Diamond<int>::`vbase dtor'(void)",
+    );
+    test(
+        "??_D?$Diamond@H@ns@@QEAAXXZ",
+        "void __cdecl ns::Diamond<int>::`vbase dtor'(void)",
+    );
     test(
         "??_EBase@@UEAAPEAXI@Z",
         "virtual void * __cdecl Base::`vector deleting dtor'(unsigned int)",
@@ -1752,6 +1990,14 @@ fn test_operators() {
         "??_KBase@@UEAAPEAXI@Z",
         "virtual void * __cdecl Base::`virtual displacement map'(unsigned int)",
     );
+    test(
+        "??_K?$Base@H@@UEAAPEAXI@Z",
+        "virtual void * __cdecl Base<int>::`virtual displacement map'(unsigned int)",
+    );
+    test(
+        "??_K?$Base@V?$Diamond@H@@@@UEAAPEAXI@Z",
+        "virtual void * __cdecl Base<class Diamond<int>>::`virtual displacement map'(unsigned int)",
+    );
     test(
         "??_LBase@@UEAAPEAXI@Z",
         "virtual void * __cdecl Base::`eh vector ctor iterator'(unsigned int)",
@@ -1769,6 +2015,18 @@ fn test_operators() {
         "void __thiscall SomeTemplate<int>::`copy ctor closure'(void)",
     );
     test("??_SBase@@6B@", "const Base::`local vftable'");
+    test(
+        "??_SDerived@@6BBase@@@",
+        "const Derived::`local vftable'{for `Base'}",
+    );
+    test(
+        "??_SDerived@@6BBase@@Other@@@",
+        "const Derived::`local vftable'{for `Base's `Other'}",
+    );
+    test(
+        "??_SDerived@ns@@6BBase@1@@",
+        "const ns::Derived::`local vftable'{for `ns::Base'}",
+    );
     test(
         "??_TDerived@@QEAAXXZ",
         "void __cdecl Derived::`local vftable ctor closure'(void)",
@@ -1787,12 +2045,41 @@ fn test_operators() {
         "??_R1A@?0A@EA@Base@@8",
         "Base::`RTTI Base Class Descriptor at (0, -1, 0, 64)'",
     );
+    test(
+        "??_R1A@?0A@EA@?$Base@H@@8",
+        "Base<int>::`RTTI Base Class Descriptor at (0, -1, 0, 64)'",
+    );
+    test(
+        "??_R1BA@?0A@EA@?$Base@UX@@@ns@@8",
+        "ns::Base<struct X>::`RTTI Base Class Descriptor at (16, -1, 0, 64)'",
+    );
+    // the template arguments have their own back-reference table
+    test(
+        "??_R1A@?0A@EA@?$Base@V?$Inner@H@@V1@@@8",
+        "Base<class Inner<int>, class Inner<int>>::`RTTI Base Class Descriptor at (0, -1, 0, 64)'",
+    );
     test("??_R2Base@@8", "Base::`RTTI Base Class Array'");
     test("??_R3Base@@8", "Base::`RTTI Class Hierarchy Descriptor'");
     test(
         "??_R4Base@@6B@",
         "const Base::`RTTI Complete Object Locator'",
     );
+    test(
+        "??_R4Derived@@6BBase@@@",
+        "const Derived::`RTTI Complete Object Locator'{for `Base'}",
+    );
+    test(
+        "??_R4D@@6BB@@C@@@",
+        "const D::`RTTI Complete Object Locator'{for `B's `C'}",
+    );
+    test("??_7D@@6BB@@C@@@", "const D::`vftable'{for `B's `C'}");
+    // the path of base classes must be terminated
+    for mangled_name in ["??_R4Derived@@6BBase@@", "??_R4D@@6BB@@C@@", "??_7D@@6BB@@"] {
+        assert!(
+            crate::demangle(mangled_name, Flags::default()).is_err(),
+            "{mangled_name}"
+        );
+    }
     test(
         "??__EFoo@@YAXXZ",
         "void __cdecl `dynamic initializer for 'Foo''(void)",
@@ -1801,6 +2088,18 @@ fn test_operators() {
         "??__E?i@C@@0HA@@YAXXZ",
         "void __cdecl `dynamic initializer for `private: static int C::i''(void)",
     );
+    test(
+        "??__E?arr@klass@@2Y01HA@@YAXXZ",
+        "void __cdecl `dynamic initializer for `public: static int klass::arr[2]''(void)",
+    );
+    test(
+        "??__E?arr@klass@@2PAY01HA@@YAXXZ",
+        "void __cdecl `dynamic initializer for `public: static int (*klass::arr)[2]''(void)",
+    );
+    test(
+        "??__F?arr@klass@@2Y01HA@@YAXXZ",
+        "void __cdecl `dynamic atexit destructor for `public: static int klass::arr[2]''(void)",
+    );
     test(
         "??__FFoo@@YAXXZ",
         "void __cdecl `dynamic atexit destructor for 'Foo''(void)",
@@ -1894,6 +2193,26 @@ fn test_options() {
         "x",
         "x",
     );
+    test_options(
+        "?member_pointer@@YAXV?$C@P8Z@@AEXXZ@@@Z",
+        "void __cdecl member_pointer(class C<void (__thiscall Z::*)(void)>)",
+        "void member_pointer(class C<void (Z::*)(void)>)",
+        "__cdecl member_pointer(class C<(__thiscall Z::*)(void)>)",
+        "void __cdecl member_pointer(class C<void (__thiscall Z::*)(void)>)",
+        "void __cdecl member_pointer(class C<void (__thiscall Z::*)(void)>)",
+        "void __cdecl member_pointer(class C<void (__thiscall Z::*)(void)>)",
+        "member_pointer(class C<(Z::*)(void)>)",
+    );
+    test_options(
+        "?f@@YAXP8Z@@AEXXZ@Z",
+        "void __cdecl f(void (__thiscall Z::*)(void))",
+        "void f(void (Z::*)(void))",
+        "__cdecl f((__thiscall Z::*)(void))",
+        "void __cdecl f(void (__thiscall Z::*)(void))",
+        "void __cdecl f(void (__thiscall Z::*)(void))",
+        "void __cdecl f(void (__thiscall Z::*)(void))",
+        "f((Z::*)(void))",
+    );
 }
 
 #[test]
@@ -2008,6 +2327,29 @@ fn test_return_qualifiers() {
         "int (__cdecl *const * __cdecl g4(void))(int)",
     );
     test("?h1@@YAAIAHXZ", "int &__restrict __cdecl h1(void)");
+    test("?h2@@YAPIAHXZ", "int *__restrict __cdecl h2(void)");
+    test("?h3@@YAPEIBHXZ", "int const *__restrict __cdecl h3(void)");
+    test(
+        "?h4@@YAPEIAPEIAHXZ",
+        "int *__restrict *__restrict __cdecl h4(void)",
+    );
+    test("?h5@@YAPEIAY01HXZ", "int (*__restrict __cdecl h5(void))[2]");
+    test(
+        "?h6@@YAPEIAP6AHXZXZ",
+        "int (__cdecl **__restrict __cdecl h6(void))(void)",
+    );
+    do_test(
+        "?h2@@YAPIAHXZ",
+        "int *__restrict h2(void)",
+        false,
+        Flags::NO_CALLING_CONVENTION,
+    );
+    do_test(
+        "?h2@@YAPIAHXZ",
+        "int *restrict cdecl h2(void)",
+        false,
+        Flags::NO_LEADING_UNDERSCORES,
+    );
 }
 
 #[test]
@@ -2684,11 +3026,32 @@ fn test_string_literals() {
     test("??_C@_0CG@HJGBPLNO@l?$AAo?$AAo?$AAk?$AAA?$AAh?$AAe?$AAa?$AAd?$AAH?$AAa?$AAr?$AAd?$AAB?$AAr?$AAe?$AAa?$AAk?$AA?$AA?$AA@", "u\"lookAheadHardBreak\"");
     test("??_C@_0CG@HJGBPLNO@l?$AAo?$AAo?$AAk?$AAA?$AAh?$AAe?$AAa?$AAd?$AAH?$AAa?$AAr?$AAd?$AAB?$AAr?$AAe?$AA@", "u\"lookAheadHardBre\"...");
     test("??_C@_05LABPAAN@b?$AA?$AA?$AA?$AA?$AA@", "u\"b\\0\"");
+    test("??_C@_00CNPNBAHC@?$AA@", "\"\"");
+    test("??_C@_11LOCGONAA@?$AA?$AA@", "L\"\"");
+    test("??_C@_03LOCGONAA@?$AA?$AA?$AA?$AA@", "U\"\"");
+    // the declared length is 1, but the terminator was cut off
+    test("??_C@_00CNPNBAHC@@", "\"\"...");
     test("??_C@_0CC@MBPKDIAM@a?$AA?$AA?$AAb?$AA?$AA?$AAc?$AA?$AA?$AAd?$AA?$AA?$AAe?$AA?$AA?$AAf?$AA?$AA?$AAg?$AA?$AA?$AAh?$AA?$AA?$AA@", "u\"a\\0b\\0c\\0d\\0e\\0f\\0g\\0h\\0\"...");
     test(
         "??_C@_07LJGFEJEB@D3?$CC?$BB?$AA?$AA?$AA?$AA@)",
         "U\"\\x11223344\"",
     );
+    test(
+        "??_C@_0BA@AAAAAAAA@a?$AA?$AA?$AA?$AA?$AA?$AA?$AAb?$AA?$AA?$AA?$AA?$AA?$AA?$AA@",
+        "U\"a\\0b\"",
+    );
+    test(
+        "??_C@_0BE@AAAAAAAA@a?$AA?$AA?$AA?$AA?$AA?$AA?$AAb?$AA?$AA?$AAc?$AA?$AA?$AA?$AA?$AA?$AA?$AA@",
+        "U\"a\\0bc\"",
+    );
+    test(
+        "??_C@_0BA@AAAAAAAA@?$AA?$AA?$AA?$AAa?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA@",
+        "U\"\\0a\\0\"",
+    );
+    test(
+        "??_C@_0M@AAAAAAAA@?$AA?$AB?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA?$AA@",
+        "U\"\\x100\\0\"",
+    );
     test(
         "??_C@_0GAAAAAAAA@GPLEPFHO@01234567890123456789012345678901@",
         "\"01234567890123456789012345678901\"...",
@@ -2753,6 +3116,14 @@ fn test_template_callback() {
         "??$WrapFnRef@$1?VoidFn@@YAXXZ@@YAXXZ",
         "void __cdecl WrapFnRef<&void __cdecl VoidFn(void)>(void)",
     );
+    test(
+        "??$WrapFnPtr@$1?VoidFn@@$$J0YAXXZ@@YAXXZ",
+        "void __cdecl WrapFnPtr<&extern \"C\" void __cdecl VoidFn(void)>(void)",
+    );
+    test(
+        "??$f@P6AXXZ$1?g@@$$J0YAXXZ@@YAXXZ",
+        "void __cdecl f<void (__cdecl *)(void), &extern \"C\" void __cdecl g(void)>(void)",
+    );
     test(
         "??$WrapFnPtr@$1?VoidStaticMethod@Thing@@SAXXZ@@YAXXZ",
         "void __cdecl WrapFnPtr<&public: static void __cdecl Thing::VoidStaticMethod(void)>(void)",
@@ -2779,6 +3150,25 @@ fn test_templates_memptrs_2() {
         "?p2@@3U?$K@UP@@$GA@A@?0@@A",
         "struct K<struct P, {0, 0, -1}> p2",
     );
+    test(
+        "?p3@@3U?$K@UP@@$G?0?3?7@@A",
+        "struct K<struct P, {-1, -4, -8}> p3",
+    );
+    // the pointee qualifiers of a data member pointer are carried by its type
+    // parameter, the argument itself only encodes offsets
+    test("?m3@@3U?$K@PRM@@H$0A@@@A", "struct K<int const M::*, 0> m3");
+    test(
+        "?o3@@3U?$K@PRO@@H$FA@?0@@A",
+        "struct K<int const O::*, {0, -1}> o3",
+    );
+    test(
+        "?p3@@3U?$K@PTP@@H$GA@A@?0@@A",
+        "struct K<int const volatile P::*, {0, 0, -1}> p3",
+    );
+    test(
+        "?p4@@3U?$K@PESP@@H$GA@A@?0@@A",
+        "struct K<int volatile P::*, {0, 0, -1}> p4",
+    );
     test("??0?$ClassTemplate@$J??_9MostGeneral@@$BA@AEA@M@3@@QAE@XZ", "__thiscall ClassTemplate<{[thunk]: __thiscall MostGeneral::`vcall'{0, {flat}}, 0, 12, 4}>::ClassTemplate<{[thunk]: __thiscall MostGeneral::`vcall'{0, {flat}}, 0, 12, 4}>(void)");
 }
 
@@ -2813,6 +3203,8 @@ fn test_templates_memptrs() {
     );
     test("??$CallMethod@UU@@$J??_91@$BA@AEA@A@A@@@YAXAAUU@@@Z", "void __cdecl CallMethod<struct U, {[thunk]: __thiscall U::`vcall'{0, {flat}}, 0, 0, 0}>(struct U &)");
     test("??$CallMethod@UU@@$J?f@1@QAEXXZA@A@A@@@YAXAAUU@@@Z", "void __cdecl CallMethod<struct U, {public: void __thiscall U::f(void), 0, 0, 0}>(struct U &)");
+    test("??$CallMethod@UU@@$J?f@1@QAEXXZ?0?3?7@@YAXAAUU@@@Z", "void __cdecl CallMethod<struct U, {public: void __thiscall U::f(void), -1, -4, -8}>(struct U &)");
+    test("??$CallMethod@UU@@$J??_91@$BA@AE?0?3?7@@YAXAAUU@@@Z", "void __cdecl CallMethod<struct U, {[thunk]: __thiscall U::`vcall'{0, {flat}}, -1, -4, -8}>(struct U &)");
     test(
         "??$CallMethod@UV@@$0A@@@YAXAAUV@@@Z",
         "void __cdecl CallMethod<struct V, 0>(struct V &)",
@@ -3156,6 +3548,21 @@ fn test_templates() {
         "??$?0N@?$Foo@H@@QEAA@N@Z",
         "__cdecl Foo<int>::Foo<int><double>(double)",
     );
+    // a nullptr_t type argument
+    test(
+        "??0?$C@$$T@@QEAA@XZ",
+        "public: __cdecl C<std::nullptr_t>::C<std::nullptr_t>(void)",
+    );
+    test(
+        "??$f@$$T$$T@@YAX$$T@Z",
+        "void __cdecl f<std::nullptr_t, std::nullptr_t>(std::nullptr_t)",
+    );
+    // a nullptr value argument is mangled the same as a null integer
+    test("??0?$C@$0A@@@QEAA@XZ", "public: __cdecl C<0>::C<0>(void)");
+    test(
+        "??0?$C@$M$$T0A@$$T@@QEAA@XZ",
+        "public: __cdecl C<0, std::nullptr_t>::C<0, std::nullptr_t>(void)",
+    );
 }
 
 #[test]
@@ -3173,6 +3580,28 @@ fn test_thunks() {
         "??_9Base@@$B7AA",
         "[thunk]: __cdecl Base::`vcall'{8, {flat}}",
     );
+    test(
+        "??_E?$Base@H@@$4PPPPPPPM@A@AEPAXI@Z",
+        "[thunk]: public: virtual void * __thiscall Base<int>::`vector deleting dtor'`vtordisp{-4, 0}'(unsigned int)",
+    );
+    test(
+        "??_G?$Base@H@@$4PPPPPPPM@A@AEPAXI@Z",
+        "[thunk]: public: virtual void * __thiscall Base<int>::`scalar deleting dtor'`vtordisp{-4, 0}'(unsigned int)",
+    );
+    test(
+        "??_E?$Base@H@@G3AEPAXI@Z",
+        "[thunk]: private: void * __thiscall Base<int>::`vector deleting dtor'`adjustor{4}'(unsigned int)",
+    );
+    test(
+        "??_E?$Base@H@@$R4PPPPPPPM@A@BA@A@AEPAXI@Z",
+        "[thunk]: public: virtual void * __thiscall Base<int>::`vector deleting dtor'`vtordispex{-4, 0, 16, 0}'(unsigned int)",
+    );
+    do_test(
+        "??_E?$Base@H@@$4PPPPPPPM@A@AEPAXI@Z",
+        "Base<int>::`vector deleting dtor'`vtordisp{-4, 0}'",
+        false,
+        Flags::NAME_ONLY,
+    );
 }
 
 #[test]
@@ -3182,6 +3611,16 @@ fn test_windows() {
     test("?f2@@YIXXZ", "void __fastcall f2(void)");
     test("?f1@@YGXXZ", "void __stdcall f1(void)");
     test("?f5@@YCXXZ", "void __pascal f5(void)");
+    // __declspec(allocator) and __declspec(restrict) do not participate in name
+    // mangling, so annotated functions are indistinguishable from plain ones
+    test(
+        "?my_malloc@@YAPEAX_K@Z",
+        "void * __cdecl my_malloc(unsigned __int64)",
+    );
+    test(
+        "?allocate@?$allocator@H@std@@QEAAPEAH_K@Z",
+        "public: int * __cdecl std::allocator<int>::allocate(unsigned __int64)",
+    );
 }
 
 #[test]
@@ -3309,11 +3748,18 @@ fn test_name_only() {
     test_option("??$f@US@@$1?g@1@QEAAXXZ@@YAXXZ", "f<S, &S::g>");
     test_option("?foo_sad@@YAXSEAD@Z", "foo_sad");
     test_option("??$WrapFnPtr@$1?VoidFn@@YAXXZ@@YAXXZ", "WrapFnPtr<&VoidFn>");
+    test_option(
+        "??$WrapFnPtr@$1?VoidFn@@$$J0YAXXZ@@YAXXZ",
+        "WrapFnPtr<&VoidFn>",
+    );
     test_option("??$CallMethod@UM@@$0A@@@YAXAAUM@@@Z", "CallMethod<M, 0>");
     test_option("?fun@@YAXU?$UUIDType1@Uuuid@@$1?_GUID_12345678_1234_1234_1234_1234567890ab@@3U__s_GUID@@B@@@Z", "fun");
     test_option("?abc_foo@@YA?AV?$A@DV?$B@D@N@@V?$C@D@2@@N@@XZ", "abc_foo");
     test_option("?f2@@YA?BUS@@XZ", "f2");
     test_option("??Hfoo@@QAEHH@Z", "foo::operator+");
+    test_option("??Qklass@@QEAAHH@Z", "klass::operator,");
+    test_option("??Rklass@@QEAAHXZ", "klass::operator()");
+    test_option("??$?RH@klass@@QEAAHH@Z", "klass::operator()<int>");
     test_option("?M@?1??L@@YAHXZ@4HA", "`L'::`2'::M");
     test_option("?h2@@3QBHB", "h2");
     test_option(
@@ -3456,68 +3902,2197 @@ fn test_name_only() {
 }
 
 #[test]
-fn test_unicode() {
-    test(".?AUМосква@@", "struct Москва `RTTI Type Descriptor Name'");
-    test(".?AU東京@@", "struct 東京 `RTTI Type Descriptor Name'");
-}
+fn test_size_t_aliases() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::SIZE_T_ALIASES);
+    };
 
-#[test]
-fn test_auto() {
-    test("?_Do_call@?$_Func_impl_no_alloc@V<lambda_1>@?6???$?RV<lambda_6>@?EF@??OnEvent@Interface@@UEAAXAEBVEvent@@@Z@@<lambda_3>@?DB@??OnEvent@Interface@@UEAAXAEBVEvent@@@Z@QEBA?A_PV<lambda_6>@?EF@??34@UEAAX0@Z@_N@Z@I$$V@std@@EEAAIXZ", "private: virtual unsigned int __cdecl std::_Func_impl_no_alloc<class `public: auto __cdecl `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`49'::<lambda_3>::operator()<class `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`69'::<lambda_6>>(class `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`69'::<lambda_6>, bool) const'::`7'::<lambda_1>, unsigned int>::_Do_call(void)");
-    test("??$emplace_back@AEBM@?$vector@MV?$allocator@M@std@@@std@@QEAA?A_TAEBM@Z", "public: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::emplace_back<float const &>(float const &)");
-    test("??$_Emplace_back_with_unused_capacity@AEBM@?$vector@MV?$allocator@M@std@@@std@@AEAA?A_TAEBM@Z", "private: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::_Emplace_back_with_unused_capacity<float const &>(float const &)");
-    test("??$emplace_back@N@?$vector@NV?$allocator@N@std@@@std@@QEAA?A_T$$QEAN@Z", "public: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::emplace_back<double>(double &&)");
-    test("??$_Emplace_back_with_unused_capacity@N@?$vector@NV?$allocator@N@std@@@std@@AEAA?A_T$$QEAN@Z", "private: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::_Emplace_back_with_unused_capacity<double>(double &&)");
-    test("??$emplace_back@AEBN@?$vector@NV?$allocator@N@std@@@std@@QEAA?A_TAEBN@Z", "public: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::emplace_back<double const &>(double const &)");
-    test("??$_Emplace_back_with_unused_capacity@AEBN@?$vector@NV?$allocator@N@std@@@std@@AEAA?A_TAEBN@Z", "private: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::_Emplace_back_with_unused_capacity<double const &>(double const &)");
-    test(
-        "??$_To_address@PEAN@std@@YA?A_PAEBQEAN@Z",
-        "auto __cdecl std::_To_address<double *>(double *const &)",
-    );
-    test(
-        "??$_To_address@PEAM@std@@YA?A_PAEBQEAM@Z",
-        "auto __cdecl std::_To_address<float *>(float *const &)",
+    test_option(
+        "??Aklass@@QEAAH_K@Z",
+        "public: int __cdecl klass::operator[](size_t)",
     );
-    test(
-        "??$_Get_unwrapped@AEBQEAM@std@@YA?A_TAEBQEAM@Z",
-        "decltype(auto) __cdecl std::_Get_unwrapped<float *const &>(float *const &)",
+    test_option("?f@@YA_J_J_K@Z", "ptrdiff_t __cdecl f(ptrdiff_t, size_t)");
+    test_option("?x@@3PEB_KEB", "size_t const *x");
+    test_option("??$f@_K@@YAXXZ", "void __cdecl f<size_t>(void)");
+    do_test(
+        "?f@@YA_J_J_K@Z",
+        "__int64 __cdecl f(__int64, unsigned __int64)",
+        false,
+        Flags::default(),
     );
-    test(
-        "??$_Get_unwrapped@AEBQEAN@std@@YA?A_TAEBQEAN@Z",
-        "decltype(auto) __cdecl std::_Get_unwrapped<double *const &>(double *const &)",
+}
+
+#[test]
+fn test_no_arguments() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::NO_ARGUMENTS);
+    };
+
+    test_option("?foo@@YAXI@Z", "void __cdecl foo");
+    test_option("?world@hello@@QEDAXXZ", "public: void __cdecl hello::world");
+    test_option("?f@S@@QEGBAXX_E", "public: void __cdecl S::f");
+    test_option(
+        "??$Foo@H@?$BoolTemplate@$00@@QEAAXH@Z",
+        "public: void __cdecl BoolTemplate<1>::Foo<int>",
     );
-    test("??$emplace_back@AEBH@?$vector@HV?$allocator@H@std@@@std@@QEAA?A_TAEBH@Z", "public: decltype(auto) __cdecl std::vector<int, class std::allocator<int>>::emplace_back<int const &>(int const &)");
-    test("??$_Emplace_back_with_unused_capacity@AEBH@?$vector@HV?$allocator@H@std@@@std@@AEAA?A_TAEBH@Z", "private: decltype(auto) __cdecl std::vector<int, class std::allocator<int>>::_Emplace_back_with_unused_capacity<int const &>(int const &)");
-    test(
-        "??$_Get_unwrapped@AEBQEAH@std@@YA?A_TAEBQEAH@Z",
-        "decltype(auto) __cdecl std::_Get_unwrapped<int *const &>(int *const &)",
+    test_option(
+        "??Bklass@@QEAAHXZ",
+        "public: int __cdecl klass::operator int",
     );
-    test(
-        "??$_To_address@PEAH@std@@YA?A_PAEBQEAH@Z",
-        "auto __cdecl std::_To_address<int *>(int *const &)",
+    test_option(
+        "?j@@3P6GHCE@ZA",
+        "int (__stdcall *j)(signed char, unsigned char)",
     );
-    test("??$_Get_unwrapped@AEBQEAV?$vector@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@V?$allocator@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@2@@std@@@std@@YA?A_TAEBQEAV?$vector@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@V?$allocator@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@2@@0@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<class std::vector<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>, class std::allocator<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>>> *const &>(class std::vector<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>, class std::allocator<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>>> *const &)");
-    test("??$_Get_unwrapped@AEBQEAV?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@std@@YA?A_TAEBQEAV?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@0@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>> *const &>(class std::vector<struct MYRECT, class std::allocator<struct MYRECT>> *const &)");
-    test("??$_Get_unwrapped@AEBQEAUMYRECT@@@std@@YA?A_TAEBQEAUMYRECT@@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<struct MYRECT *const &>(struct MYRECT *const &)");
-    test("??$emplace_back@M@?$vector@MV?$allocator@M@std@@@std@@QEAA?A_T$$QEAM@Z", "public: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::emplace_back<float>(float &&)");
-    test("??$_Emplace_back_with_unused_capacity@M@?$vector@MV?$allocator@M@std@@@std@@AEAA?A_T$$QEAM@Z", "private: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::_Emplace_back_with_unused_capacity<float>(float &&)");
-}
+    test_option("?x@@3HA", "int x");
 
-#[test]
-fn test_alloc_preserved_on_failure() {
-    let mut buffer = String::new();
-    buffer.reserve(0x1000);
-    let result = crate::demangle_into("abc", Flags::default(), &mut buffer);
-    debug_assert!(result.is_err());
-    debug_assert!(buffer.capacity() >= 0x1000);
+    // type names have no calling convention or parameter list to skip
+    test_option("?AV?$ClassA@VClassB@@@@", "class ClassA<class ClassB>");
+    test_option("?AUfoo@bar@@", "struct bar::foo");
 }
 
 #[test]
-fn test_excess_backrefs() {
-    do_test(
-		"??$prefer_fn@V?$any_executor@U?$context_as_t@AEAVexecution_context@asio@boost@@@execution@asio@boost@@U?$never_t@$0A@@blocking@detail@234@U?$prefer_only@U?$possibly_t@$0A@@blocking@detail@execution@asio@boost@@@234@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$untracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$fork_t@$0A@@relationship@detail@execution@asio@boost@@@234@U?$prefer_only@U?$continuation_t@$0A@@relationship@detail@execution@asio@boost@@@234@@execution@asio@boost@@V?$basic_executor_type@V?$allocator@X@std@@$0A@@io_context@34@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@@any_executor_base@detail@execution@asio@boost@@KA?AV?$any_executor@U?$context_as_t@AEAVexecution_context@asio@boost@@@execution@asio@boost@@U?$never_t@$0A@@blocking@detail@234@U?$prefer_only@U?$possibly_t@$0A@@blocking@detail@execution@asio@boost@@@234@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$untracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$fork_t@$0A@@relationship@detail@execution@asio@boost@@@234@U?$prefer_only@U?$continuation_t@$0A@@relationship@detail@execution@asio@boost@@@234@@234@PEBX0@Z",
-		"protected: static class boost::asio::execution::any_executor<struct boost::asio::execution::context_as_t<class boost::asio::execution_context &>, struct boost::asio::execution::detail::blocking::never_t<0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::blocking::possibly_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::untracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::fork_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::continuation_t<0>>> __cdecl boost::asio::execution::detail::any_executor_base::prefer_fn<class boost::asio::execution::any_executor<struct boost::asio::execution::context_as_t<class boost::asio::execution_context &>, struct boost::asio::execution::detail::blocking::never_t<0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::blocking::possibly_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::untracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::fork_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::continuation_t<0>>>, class boost::asio::io_context::basic_executor_type<class std::allocator<void>, 0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>>(void const *, void const *)",
-		false,
-		Flags::default()
+fn test_signature_only() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::SIGNATURE_ONLY);
+        do_test(mangled_name, demangled_name, false, Flags::NAME_ONLY);
+    };
+
+    test_option("?foo@@YAXI@Z", "foo");
+    test_option("?world@hello@@QEDAXXZ", "hello::world");
+    test_option(
+        "??$Foo@H@?$BoolTemplate@$00@@QEAAXH@Z",
+        "BoolTemplate<1>::Foo<int>",
+    );
+    test_option("??Bklass@@QEAAHXZ", "klass::operator int");
+    test_option("??$WrapFnPtr@$1?VoidFn@@YAXXZ@@YAXXZ", "WrapFnPtr<&VoidFn>");
+    test_option("?j@@3P6GHCE@ZA", "j");
+}
+
+#[test]
+fn test_strict_utf8_identifiers() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::STRICT_UTF8_IDENTIFIERS,
+        );
+    };
+    let test_invalid = |mangled_name: &str| {
+        let result = crate::demangle(mangled_name, Flags::STRICT_UTF8_IDENTIFIERS);
+        assert!(
+            matches!(result, Err(crate::Error::InvalidSimpleString)),
+            "'{mangled_name}' <-- mangled string\n{result:?} <-- result",
+        );
+    };
+
+    test_option("?x@@3HA", "int x");
+    test_option("?caf\u{e9}@@3HA", "int caf\u{e9}");
+    test_option("??_C@_05OMLEGLOC@h?$AAi?$AA?$AA?$AA@", "u\"hi\"");
+    test_invalid("?x\0y@@3HA");
+    test_invalid("?x\n@@3HA");
+    test_invalid("?x@ns\u{1b}[31m@@3HA");
+    test_invalid("?f@@YAXVbad\t@@@Z");
+    test_invalid("??$f@Vbad\x7f@@@@YAXXZ");
+    do_test("?x\n@@3HA", "int x\n", false, Flags::default());
+}
+
+#[test]
+fn test_build_stable_names() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::BUILD_STABLE_NAMES,
+        );
+    };
+    let test_equal = |lhs: &str, rhs: &str| {
+        assert_ne!(
+            crate::demangle(lhs, Flags::default()).unwrap(),
+            crate::demangle(rhs, Flags::default()).unwrap()
+        );
+        assert_eq!(
+            crate::demangle(lhs, Flags::BUILD_STABLE_NAMES).unwrap(),
+            crate::demangle(rhs, Flags::BUILD_STABLE_NAMES).unwrap()
+        );
+    };
+
+    test_option(
+        "?f@?A0x1234abcd@@YAXXZ",
+        "void __cdecl `anonymous namespace'::f(void)",
+    );
+    test_option("?M@?1??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`#'::M");
+    test_option(
+        "??$use_lambda_arg@V<lambda_1>@?0??call_with_lambda_arg1@@YAXXZ@@@YAXV<lambda_1>@?0??call_with_lambda_arg1@@YAXXZ@@Z",
+        "void __cdecl use_lambda_arg<class `void __cdecl call_with_lambda_arg1(void)'::`#'::<lambda>>(class `void __cdecl call_with_lambda_arg1(void)'::`#'::<lambda>)",
+    );
+    // names which render alike are still distinct back-references
+    test_option(
+        "?f@@YAXV?$A@V<lambda_1>@?0??g@@YAXXZ@@@V?$A@V<lambda_2>@?0??g@@YAXXZ@@@VC@@V2@@Z",
+        "void __cdecl f(class A<class `void __cdecl g(void)'::`#'::<lambda>>, class A<class `void __cdecl g(void)'::`#'::<lambda>>, class C, class A<class `void __cdecl g(void)'::`#'::<lambda>>)",
+    );
+    test_option(
+        "?f@@YAXV?$A@V<lambda_1>@?0??g@@YAXXZ@@@V?$A@V<lambda_1>@?1??g@@YAXXZ@@@VC@@V2@@Z",
+        "void __cdecl f(class A<class `void __cdecl g(void)'::`#'::<lambda>>, class A<class `void __cdecl g(void)'::`#'::<lambda>>, class C, class A<class `void __cdecl g(void)'::`#'::<lambda>>)",
+    );
+    test_option(
+        "?x@@3V<lambda_invoker_cdecl>@@A",
+        "class <lambda_invoker_cdecl> x",
+    );
+
+    test_equal(
+        "??R<lambda_1>@?0??main@@YAHXZ@QBE@XZ",
+        "??R<lambda_7>@?3??main@@YAHXZ@QBE@XZ",
+    );
+    test_equal(
+        "??R<lambda_9b2e4f1a>@?0??main@@YAHXZ@QBE@XZ",
+        "??R<lambda_0c77d1e2>@?0??main@@YAHXZ@QBE@XZ",
+    );
+    test_equal("?M@?1??L@@YAHXZ@4HA", "?M@?BA@??L@@YAHXZ@4HA");
+}
+
+#[test]
+fn test_no_conversion_operator_target() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::NO_CONVERSION_OPERATOR_TARGET,
+        );
+    };
+
+    test_option(
+        "??BOps@@QAEHXZ",
+        "public: __thiscall Ops::operator int(void)",
+    );
+    test_option(
+        "??BConstOps@@QAE?BHXZ",
+        "public: __thiscall ConstOps::operator int const(void)",
+    );
+    test_option(
+        "??BVolatileOps@@QAE?CHXZ",
+        "public: __thiscall VolatileOps::operator int volatile(void)",
+    );
+    test_option(
+        "??BConstVolatileOps@@QAE?DHXZ",
+        "public: __thiscall ConstVolatileOps::operator int const volatile(void)",
+    );
+    test_option(
+        "??$?BH@TemplateOps@@QAEHXZ",
+        "public: __thiscall TemplateOps::operator<int> int(void)",
+    );
+    test_option(
+        "??$?BN@TemplateOps@@QAENXZ",
+        "public: __thiscall TemplateOps::operator<double> double(void)",
+    );
+    // functions which aren't conversion operators keep their return type
+    test_option("?f@@YAHXZ", "int __cdecl f(void)");
+}
+
+#[test]
+fn test_split_nested_name_separator() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::SPLIT_NESTED_NAME_SEPARATOR,
+        );
+    };
+
+    test_option("?a@@3HA", "int a");
+    test_option("?b@N@@3HA", "int N.b");
+    test_option(
+        "?anonymous@?A@N@@3HA",
+        "int N.`anonymous namespace'.anonymous",
+    );
+    test_option("?d@foo@@0FB", "private: static short const foo.d");
+    test_option("??0foo@@QAE@XZ", "public: __thiscall foo.foo(void)");
+    test_option("??1foo@@QAE@XZ", "public: __thiscall foo.~foo(void)");
+    test_option(
+        "?static_method@foo@@SAPAV1@XZ",
+        "public: static class foo * __cdecl foo.static_method(void)",
+    );
+    test_option(
+        "??$?HH@S@@QEAAAEANH@Z",
+        "public: double & __cdecl S.operator+<int>(int)",
+    );
+    test_option("?M@?1??L@@YAHXZ@4HA", "int `int __cdecl L(void)'.`2'.M");
+    do_test(
+        "?M@?1??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'.`#'.M",
+        false,
+        Flags::SPLIT_NESTED_NAME_SEPARATOR | Flags::BUILD_STABLE_NAMES,
+    );
+    test_option("?mp@@3PQS@N@@HQ12@", "int N.S.*mp");
+}
+
+#[test]
+fn test_drop_void_return() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::DROP_VOID_RETURN);
+    };
+    let test_contrast = |mangled_name: &str, drop_void: &str, no_return: &str| {
+        do_test(mangled_name, drop_void, false, Flags::DROP_VOID_RETURN);
+        do_test(mangled_name, no_return, false, Flags::NO_RETURN_TYPE);
+    };
+
+    test_option("?foo@@YAXH@Z", "__cdecl foo(int)");
+    test_option("?bar@@YAHXZ", "int __cdecl bar(void)");
+    test_option("?f@C@@QBEXXZ", "public: __thiscall C::f(void) const");
+    test_option("??0foo@@QAE@XZ", "public: __thiscall foo::foo(void)");
+    test_option("?x@@3P6AXXZA", "void (__cdecl *x)(void)");
+
+    test_contrast(
+        "?foo@@YAXP6AXH@Z@Z",
+        "__cdecl foo(void (__cdecl *)(int))",
+        "__cdecl foo((__cdecl *)(int))",
+    );
+    test_contrast(
+        "?f@@YAP6AXXZXZ",
+        "void (__cdecl * __cdecl f(void))(void)",
+        "__cdecl f(void)",
+    );
+    test_contrast("?bar@@YAHXZ", "int __cdecl bar(void)", "__cdecl bar(void)");
+}
+
+#[test]
+fn test_canonical_integer_literals() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::CANONICAL_INTEGER_LITERALS,
+        );
+    };
+
+    test_option(
+        "??0?$IntTemplate@$0A@@@QEAA@XZ",
+        "public: __cdecl IntTemplate<0>::IntTemplate<0>(void)",
+    );
+    test_option(
+        "??0?$IntTemplate@$0L@@@QEAA@XZ",
+        "public: __cdecl IntTemplate<11>::IntTemplate<11>(void)",
+    );
+    test_option(
+        "??0?$IntTemplate@$0BAA@@@QEAA@XZ",
+        "public: __cdecl IntTemplate<0x100>::IntTemplate<0x100>(void)",
+    );
+    test_option(
+        "??0?$IntTemplate@$0PPPP@@@QEAA@XZ",
+        "public: __cdecl IntTemplate<0xFFFF>::IntTemplate<0xFFFF>(void)",
+    );
+    test_option(
+        "??0?$IntTemplate@$0?L@@@QEAA@XZ",
+        "public: __cdecl IntTemplate<-11>::IntTemplate<-11>(void)",
+    );
+    test_option(
+        "??0?$UnsignedIntTemplate@$0PPPPPPPP@@@QEAA@XZ",
+        "public: __cdecl UnsignedIntTemplate<0xFFFFFFFF>::UnsignedIntTemplate<0xFFFFFFFF>(void)",
+    );
+    test_option(
+        "??0?$LongLongTemplate@$0?IAAAAAAAAAAAAAAA@@@QEAA@XZ",
+        "public: __cdecl LongLongTemplate<-0x8000000000000000>::LongLongTemplate<-0x8000000000000000>(void)",
+    );
+    test_option(
+        "??0?$LongLongTemplate@$0HPPPPPPPPPPPPPPP@@@QEAA@XZ",
+        "public: __cdecl LongLongTemplate<0x7FFFFFFFFFFFFFFF>::LongLongTemplate<0x7FFFFFFFFFFFFFFF>(void)",
+    );
+    test_option(
+        "??0?$UnsignedLongLongTemplate@$0?0@@QEAA@XZ",
+        "public: __cdecl UnsignedLongLongTemplate<-1>::UnsignedLongLongTemplate<-1>(void)",
+    );
+    test_option("?x@@3PAY0BAA@HA", "int (*x)[0x100]");
+}
+
+#[test]
+fn test_show_function_pointer_params_inline() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::SHOW_FUNCTION_POINTER_PARAMS_INLINE,
+        );
+    };
+
+    test_option(
+        "?h3@@YAP6APAHPAH0@ZP6APAH00@Z10@Z",
+        "int * (__cdecl * __cdecl h3(\n    int * (__cdecl *)(int *, int *),\n    int * (__cdecl *)(int *, int *),\n    int *\n))(int *, int *)",
+    );
+    test_option("?f@@YAXXZ", "void __cdecl f(void)");
+    test_option("?f@@YAXHZZ", "void __cdecl f(\n    int,\n    ...\n)");
+    test_option("?f@@YAXZZ", "void __cdecl f(\n    ...\n)");
+    test_option("?x@@3HA", "int x");
+    test_option("?f@?1??g@@YAXH@Z@4HA", "int `void __cdecl g(int)'::`2'::f");
+    test_option(
+        "??$f@$1?g@@YAXHH@Z@@YAXH@Z",
+        "void __cdecl f<&void __cdecl g(int, int)>(\n    int\n)",
+    );
+    test_option(
+        "??$f@$$A6AXHH@Z@@YAXH@Z",
+        "void __cdecl f<void __cdecl(int, int)>(\n    int\n)",
+    );
+    test_option(
+        "?f@@YAXP6AXHZZ@Z",
+        "void __cdecl f(\n    void (__cdecl *)(int, ...)\n)",
+    );
+}
+
+#[test]
+fn test_no_rtti_quote_decoration() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::NO_RTTI_QUOTE_DECORATION,
+        );
+    };
+
+    test_option("??_R0?AUBase@@@8", "struct Base RTTI Type Descriptor");
+    test_option(
+        "??_R1A@?0A@EA@Base@@8",
+        "Base::RTTI Base Class Descriptor at (0, -1, 0, 64)",
+    );
+    test_option(
+        "??_R1A@?0A@EA@?$Base@H@@8",
+        "Base<int>::RTTI Base Class Descriptor at (0, -1, 0, 64)",
+    );
+    test_option("??_R2Base@@8", "Base::RTTI Base Class Array");
+    test_option("??_R3Base@@8", "Base::RTTI Class Hierarchy Descriptor");
+    test_option("??_R4Base@@6B@", "const Base::RTTI Complete Object Locator");
+    test_option(".?AUBase@@", "struct Base RTTI Type Descriptor Name");
+    test_option("??_7Base@@6B@", "const Base::`vftable'");
+
+    // classification comes from the symbol's structure, not from either spelling of its name
+    assert_eq!(crate::classify("??_R3Base@@8").unwrap(), SymbolKind::Rtti);
+    assert_eq!(
+        crate::classify("?RTTI Class Hierarchy Descriptor@Base@@3HA").unwrap(),
+        SymbolKind::Variable
+    );
+    assert_eq!(
+        crate::classify("?`RTTI Class Hierarchy Descriptor'@Base@@3HA").unwrap(),
+        SymbolKind::Variable
+    );
+}
+
+#[test]
+fn test_render_this_parameter() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::RENDER_THIS_PARAMETER,
+        );
+    };
+
+    test_option(
+        "?foo@klass@@QEAAXXZ",
+        "public: void __cdecl klass::foo(klass *this)",
+    );
+    test_option(
+        "?foo@klass@@QEBAXH@Z",
+        "public: void __cdecl klass::foo(klass const *this, int) const",
+    );
+    test_option(
+        "?foo@klass@@QECAXHZZ",
+        "public: void __cdecl klass::foo(klass volatile *this, int, ...) volatile",
+    );
+    test_option(
+        "?foo@?$klass@H@ns@@QEDAXH@Z",
+        "public: void __cdecl ns::klass<int>::foo(ns::klass<int> const volatile *this, int) const volatile",
+    );
+    test_option(
+        "?foo@klass@@QEGBAXH@Z",
+        "public: void __cdecl klass::foo(klass const *this, int) const &",
+    );
+    test_option(
+        "??0klass@@QEAA@XZ",
+        "public: __cdecl klass::klass(klass *this)",
+    );
+    test_option(
+        "?f@C@@W7EAAXXZ",
+        "[thunk]: public: virtual void __cdecl C::f`adjustor{8}'(C *this)",
+    );
+    test_option(
+        "?foo@klass@@SAXH@Z",
+        "public: static void __cdecl klass::foo(int)",
+    );
+    test_option("?foo@@YAXH@Z", "void __cdecl foo(int)");
+    test_option(
+        "?foo@klass@@QEIAAXXZ",
+        "public: void __cdecl klass::foo(klass *__restrict this) __restrict",
+    );
+    test_option(
+        "?foo@klass@@QEIFBAXH@Z",
+        "public: void __cdecl klass::foo(klass const __unaligned *__restrict this, int) const __restrict __unaligned",
+    );
+    do_test(
+        "?foo@klass@@QEIFBAXH@Z",
+        "public: void klass::foo(klass const *this, int) const",
+        false,
+        Flags::RENDER_THIS_PARAMETER | Flags::NO_MS_KEYWORDS,
+    );
+    do_test(
+        "?foo@b@a@@QEAAXXZ",
+        "public: void __cdecl a.b.foo(a.b *this)",
+        false,
+        Flags::RENDER_THIS_PARAMETER | Flags::SPLIT_NESTED_NAME_SEPARATOR,
+    );
+}
+
+#[test]
+fn test_flatten_nested_templates() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::FLATTEN_NESTED_TEMPLATES,
+        );
+    };
+
+    test_option(
+        "?foo_abbb@@YAXV?$A@V?$B@D@@V1@V1@@@@Z",
+        "void __cdecl foo_abbb(class A<...>)",
+    );
+    test_option(
+        "?foo_abbb@@YAXV?$A@V?$B@D@N@@V12@V12@@N@@@Z",
+        "void __cdecl foo_abbb(class N::A<...>)",
+    );
+    test_option(
+        "?foo_bt@@YAX_NV?$B@$$A6A_N_N@Z@@@Z",
+        "void __cdecl foo_bt(bool, class B<...>)",
+    );
+    test_option(
+        "??$?6H@std@@YAAEAV?$basic_ostream@DU?$char_traits@D@std@@@0@AEAV10@H@Z",
+        "class std::basic_ostream<...> & __cdecl std::operator<<<...>(class std::basic_ostream<...> &, int)",
+    );
+    test_option(
+        "?foo@?$A@H@ns@@QEAAXV?$B@H@2@@Z",
+        "public: void __cdecl ns::A<...>::foo(class ns::B<...>)",
+    );
+    test_option(
+        "??$templ_fun_with_ty_pack@$$V@@YAXXZ",
+        "void __cdecl templ_fun_with_ty_pack<>(void)",
+    );
+    // flattened names are still distinct back-references
+    test_option(
+        "?f@@YAXV?$A@H@@V?$A@N@@VC@@V2@@Z",
+        "void __cdecl f(class A<...>, class A<...>, class C, class A<...>)",
+    );
+}
+
+#[test]
+fn test_emit_tag_in_template_args() {
+    let test_options =
+        |mangled_name: &str, default: &str, no_tag: &str, emit_tag: &str, no_tag_emit_tag: &str| {
+            do_test(mangled_name, default, false, Flags::default());
+            do_test(mangled_name, no_tag, false, Flags::NO_TAG_SPECIFIER);
+            do_test(
+                mangled_name,
+                emit_tag,
+                false,
+                Flags::EMIT_TAG_IN_TEMPLATE_ARGS,
+            );
+            do_test(
+                mangled_name,
+                no_tag_emit_tag,
+                false,
+                Flags::NO_TAG_SPECIFIER | Flags::EMIT_TAG_IN_TEMPLATE_ARGS,
+            );
+        };
+
+    test_options(
+        "?x@@3PEAV?$tmpl@H@@EA",
+        "class tmpl<int> *x",
+        "tmpl<int> *x",
+        "class tmpl<int> *x",
+        "tmpl<int> *x",
+    );
+    test_options(
+        "?x@@3PEAV?$tmpl@VA@@@@EA",
+        "class tmpl<class A> *x",
+        "tmpl<A> *x",
+        "class tmpl<class A> *x",
+        "tmpl<class A> *x",
+    );
+    test_options(
+        "?f@@YAXPEAV?$tmpl@V?$inner@UA@@@@@@@Z",
+        "void __cdecl f(class tmpl<class inner<struct A>> *)",
+        "void __cdecl f(tmpl<inner<A>> *)",
+        "void __cdecl f(class tmpl<class inner<struct A>> *)",
+        "void __cdecl f(tmpl<class inner<struct A>> *)",
+    );
+}
+
+#[test]
+fn test_numeric_scope_from_zero() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::NUMERIC_SCOPE_FROM_ZERO,
+        );
+    };
+
+    test_option("?M@?0??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`0'::M");
+    test_option("?M@?1??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`1'::M");
+    test_option("?M@?9??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`9'::M");
+    test_option("?M@?L@??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`10'::M");
+    test_option("?M@?@??L@@YAHXZ@4HA", "int `int __cdecl L(void)'::`0'::M");
+    test_option("?x@?1??f@@YAXXZ@4HA", "int `void __cdecl f(void)'::`1'::x");
+    do_test(
+        "?M@?0??L@@YAHXZ@4HA",
+        "int `int __cdecl L(void)'::`#'::M",
+        false,
+        Flags::NUMERIC_SCOPE_FROM_ZERO | Flags::BUILD_STABLE_NAMES,
+    );
+}
+
+#[test]
+fn test_stable_lambda_numbers() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::STABLE_LAMBDA_NUMBERS,
+        );
+    };
+
+    // lambdas which only differ in their numbering are rendered the same
+    test_option(
+        "??R<lambda_0>@?0??PR26105@@YAHXZ@QBE@H@Z",
+        "public: __thiscall `int __cdecl PR26105(void)'::`1'::<lambda>::operator()(int) const",
+    );
+    test_option(
+        "??R<lambda_1>@?0??PR26105@@YAHXZ@QBE@H@Z",
+        "public: __thiscall `int __cdecl PR26105(void)'::`1'::<lambda>::operator()(int) const",
+    );
+    test_option(
+        "??R<lambda_1>@?0???R<lambda_0>@?0??PR26105@@YAHXZ@QBE@H@Z@QBE@H@Z",
+        "public: __thiscall `public: __thiscall `int __cdecl PR26105(void)'::`1'::<lambda>::operator()(int) const'::`1'::<lambda>::operator()(int) const",
+    );
+    test_option(
+        "??$use_lambda_arg@V<lambda_1>@?0??call_with_lambda_arg1@@YAXXZ@@@YAXV<lambda_1>@?0??call_with_lambda_arg1@@YAXXZ@@Z",
+        "void __cdecl use_lambda_arg<class `void __cdecl call_with_lambda_arg1(void)'::`1'::<lambda>>(class `void __cdecl call_with_lambda_arg1(void)'::`1'::<lambda>)",
+    );
+    // renumbered lambdas are still distinct back-references
+    test_option(
+        "?f@@YAXV?$A@V<lambda_1>@?0??g@@YAXXZ@@@V?$A@V<lambda_2>@?0??g@@YAXXZ@@@VC@@V2@@Z",
+        "void __cdecl f(class A<class `void __cdecl g(void)'::`1'::<lambda>>, class A<class `void __cdecl g(void)'::`1'::<lambda>>, class C, class A<class `void __cdecl g(void)'::`1'::<lambda>>)",
+    );
+    // names which only look like lambdas are left alone
+    test_option("?x@@3V<lambda_>@@A", "class <lambda_> x");
+    test_option("?x@@3V<lambda_x>@@A", "class <lambda_x> x");
+}
+
+#[test]
+fn test_west_const() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::WEST_CONST);
+    };
+
+    test_option("?foo_pbd@@YAXPBD@Z", "void __cdecl foo_pbd(const char *)");
+    test_option(
+        "?foo_qbd@@YAXQBD@Z",
+        "void __cdecl foo_qbd(const char *const)",
+    );
+    test_option("?x@@3QEBDEB", "const char *const x");
+    test_option("?x@@3PEDUS@@EA", "const volatile struct S *x");
+    test_option(
+        "?f@@YAXAEBVklass@@@Z",
+        "void __cdecl f(const class klass &)",
+    );
+    test_option("?x@@3PEBY01HEB", "const int (*x)[2]");
+    test_option("?x@@3QAY01PAHA", "int *(*const x)[2]");
+    test_option("?x@@3PEAY02$$CCHEA", "volatile int (*x)[3]");
+    test_option("?x@@3PEAY02$$CDHEA", "const volatile int (*x)[3]");
+    test_option(
+        "?foo@klass@@QEBAXXZ",
+        "public: void __cdecl klass::foo(void) const",
+    );
+}
+
+#[test]
+fn test_abbreviate_std() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::ABBREVIATE_STD);
+    };
+
+    test_option(
+        "?f@@YAXAEBV?$vector@V?$basic_string@DU?$char_traits@D@std@@V?$allocator@D@2@@std@@V?$allocator@V?$basic_string@DU?$char_traits@D@std@@V?$allocator@D@2@@std@@@2@@std@@@Z",
+        "void __cdecl f(class std::vector<class std::string> const &)",
+    );
+    test_option(
+        "??0?$vector@HV?$allocator@H@std@@@std@@QEAA@XZ",
+        "public: __cdecl std::vector<int>::vector<int>(void)",
+    );
+    test_option(
+        "??1?$basic_string@_WU?$char_traits@_W@std@@V?$allocator@_W@2@@std@@QEAA@XZ",
+        "public: __cdecl std::wstring::~wstring(void)",
+    );
+    test_option(
+        "?push_back@?$vector@HV?$allocator@H@std@@@std@@QEAAXAEBH@Z",
+        "public: void __cdecl std::vector<int>::push_back(int const &)",
+    );
+    test_option(
+        "?f@@YAXV?$basic_string_view@DU?$char_traits@D@std@@@std@@@Z",
+        "void __cdecl f(class std::string_view)",
+    );
+    test_option(
+        "?f@@YAXV?$list@PEAHV?$allocator@PEAH@std@@@std@@@Z",
+        "void __cdecl f(class std::list<int *>)",
+    );
+
+    // only defaulted arguments of templates in `std` are elided
+    test_option(
+        "?f@@YAXV?$vector@HV?$allocator@D@std@@@std@@@Z",
+        "void __cdecl f(class std::vector<int, class std::allocator<char>>)",
+    );
+    test_option(
+        "?f@@YAXV?$vector@HV?$allocator@H@std@@@ns@@@Z",
+        "void __cdecl f(class ns::vector<int, class std::allocator<int>>)",
+    );
+    // abbreviated names are still distinct back-references
+    test_option(
+        "?f@@YAXV?$A@V?$vector@HV?$allocator@H@std@@@std@@@@V?$A@V?$vector@H@std@@@@VC@@V2@@Z",
+        "void __cdecl f(class A<class std::vector<int>>, class A<class std::vector<int>>, class C, class A<class std::vector<int>>)",
+    );
+}
+
+#[test]
+fn test_char8_as_unsigned_char() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::CHAR8_AS_UNSIGNED_CHAR,
+        );
+    };
+
+    test_option("?f@@YAX_Q@Z", "void __cdecl f(unsigned char)");
+    test_option("?f@@YAXPEB_Q@Z", "void __cdecl f(unsigned char const *)");
+    test_option("?f@@YAX_S_U@Z", "void __cdecl f(char16_t, char32_t)");
+    test_option("?f@@YAXE@Z", "void __cdecl f(unsigned char)");
+    // char8_t is still distinct from unsigned char for back-references
+    test_option(
+        "?f@@YAXV?$A@_Q@@V?$A@E@@VC@@V2@@Z",
+        "void __cdecl f(class A<unsigned char>, class A<unsigned char>, class C, class A<unsigned char>)",
+    );
+}
+
+#[test]
+fn test_trailing_const_on_methods_only() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::TRAILING_CONST_ON_METHODS_ONLY,
+        );
+    };
+
+    test_option(
+        "?world@hello@@QEDAXXZ",
+        "public: void __cdecl hello::world(void) const",
+    );
+    test_option(
+        "?world@hello@@QEBAXXZ",
+        "public: void __cdecl hello::world(void) const",
+    );
+    test_option(
+        "?world@hello@@QECAXXZ",
+        "public: void __cdecl hello::world(void)",
+    );
+    test_option(
+        "?world@hello@@QEIAAXXZ",
+        "public: void __cdecl hello::world(void)",
+    );
+    test_option(
+        "?world@hello@@QEFAAXXZ",
+        "public: void __cdecl hello::world(void)",
+    );
+    test_option(
+        "?get@klass@@QEGBAHXZ",
+        "public: int __cdecl klass::get(void) const &",
+    );
+
+    // NO_THISTYPE drops the const too
+    do_test(
+        "?world@hello@@QEDAXXZ",
+        "public: void __cdecl hello::world(void)",
+        false,
+        Flags::NO_THISTYPE | Flags::TRAILING_CONST_ON_METHODS_ONLY,
+    );
+}
+
+#[test]
+fn test_validate_string_crc() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::VALIDATE_STRING_CRC,
+        );
+    };
+
+    test_option("??_C@_02PCEFGMJL@hi?$AA@", "\"hi\"");
+    test_option("??_C@_00CNPNBAHC@?$AA@", "\"\"");
+    test_option("??_C@_11LOCGONAA@?$AA?$AA@", "L\"\"");
+    test_option("??_C@_0CA@NFEFHIFO@0?$AA1?$AA2?$AA3?$AA4?$AA5?$AA6?$AA7?$AA8?$AA9?$AA0?$AA1?$AA2?$AA3?$AA4?$AA?$AA?$AA@", "u\"012345678901234\"");
+    test_option("??_C@_0CA@KFPHPCC@0?$AA?$AA?$AA1?$AA?$AA?$AA2?$AA?$AA?$AA3?$AA?$AA?$AA4?$AA?$AA?$AA5?$AA?$AA?$AA6?$AA?$AA?$AA?$AA?$AA?$AA?$AA@", "U\"0123456\"");
+
+    // truncated literals can't be checked
+    test_option("??_C@_0CG@AAAAAAAA@l?$AAo?$AAo?$AAk?$AAA?$AAh?$AAe?$AAa?$AAd?$AAH?$AAa?$AAr?$AAd?$AAB?$AAr?$AAe?$AA@", "u\"lookAheadHardBre\"...");
+
+    for mangled_name in [
+        "??_C@_02PCEFGMJA@hi?$AA@",
+        "??_C@_02PCEFGMJ@hi?$AA@",
+        "??_C@_02PCEFGMJ0@hi?$AA@",
+        "??_C@_11LOCGONAB@?$AA?$AA@",
+    ] {
+        assert!(crate::demangle(mangled_name, Flags::default()).is_ok());
+        assert!(matches!(
+            crate::demangle(mangled_name, Flags::VALIDATE_STRING_CRC),
+            Err(crate::Error::InvalidStringLiteral)
+        ));
+    }
+}
+
+#[test]
+fn test_no_auto_placeholder() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(
+            mangled_name,
+            demangled_name,
+            false,
+            Flags::NO_AUTO_PLACEHOLDER,
+        );
+    };
+
+    test_option(
+        "?FunctionWithLocalType@@YA?A?<auto>@@XZ",
+        "auto __cdecl FunctionWithLocalType(void)",
+    );
+    test_option("?ValueFromFunctionWithLocalType@@3ULocalType@?1??FunctionWithLocalType@@YA?A?<auto>@@XZ@A", "struct `auto __cdecl FunctionWithLocalType(void)'::`2'::LocalType ValueFromFunctionWithLocalType");
+    test_option("??$emplace_back@ABH@?$vector@HV?$allocator@H@std@@@std@@QAE?A?<decltype-auto>@@ABH@Z", "public: decltype(auto) __thiscall std::vector<int, class std::allocator<int>>::emplace_back<int const &>(int const &)");
+
+    // other custom types are unaffected
+    test_option("?f@@YA?A?<other>@@XZ", "<other> __cdecl f(void)");
+}
+
+#[test]
+fn test_no_cc_on_data() {
+    let test_option = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::NO_CC_ON_DATA);
+    };
+
+    test_option("?x@@3P6AHMNH@ZEA", "int (*x)(float, double, int)");
+    test_option("?x@@3P6AHP6AHM@ZN@ZEA", "int (*x)(int (*)(float), double)");
+    test_option("?j@@3P6GHCE@ZA", "int (*j)(signed char, unsigned char)");
+
+    // functions keep their calling conventions, even in the scope of a variable
+    test_option(
+        "?beta@@YI_N_J_W@Z",
+        "bool __fastcall beta(__int64, wchar_t)",
+    );
+    test_option("?f@@YAXP6AHH@Z@Z", "void __cdecl f(int (__cdecl *)(int))");
+    test_option(
+        "?x@?1??f@@YAXXZ@4P6AHXZA",
+        "int (*`void __cdecl f(void)'::`2'::x)(void)",
+    );
+}
+
+#[test]
+fn test_unicode() {
+    test(".?AUМосква@@", "struct Москва `RTTI Type Descriptor Name'");
+    test(".?AU東京@@", "struct 東京 `RTTI Type Descriptor Name'");
+}
+
+#[test]
+fn test_auto() {
+    test("?_Do_call@?$_Func_impl_no_alloc@V<lambda_1>@?6???$?RV<lambda_6>@?EF@??OnEvent@Interface@@UEAAXAEBVEvent@@@Z@@<lambda_3>@?DB@??OnEvent@Interface@@UEAAXAEBVEvent@@@Z@QEBA?A_PV<lambda_6>@?EF@??34@UEAAX0@Z@_N@Z@I$$V@std@@EEAAIXZ", "private: virtual unsigned int __cdecl std::_Func_impl_no_alloc<class `public: auto __cdecl `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`49'::<lambda_3>::operator()<class `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`69'::<lambda_6>>(class `public: virtual void __cdecl Interface::OnEvent(class Event const &)'::`69'::<lambda_6>, bool) const'::`7'::<lambda_1>, unsigned int>::_Do_call(void)");
+    test("??$emplace_back@AEBM@?$vector@MV?$allocator@M@std@@@std@@QEAA?A_TAEBM@Z", "public: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::emplace_back<float const &>(float const &)");
+    test("??$_Emplace_back_with_unused_capacity@AEBM@?$vector@MV?$allocator@M@std@@@std@@AEAA?A_TAEBM@Z", "private: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::_Emplace_back_with_unused_capacity<float const &>(float const &)");
+    test("??$emplace_back@N@?$vector@NV?$allocator@N@std@@@std@@QEAA?A_T$$QEAN@Z", "public: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::emplace_back<double>(double &&)");
+    test("??$_Emplace_back_with_unused_capacity@N@?$vector@NV?$allocator@N@std@@@std@@AEAA?A_T$$QEAN@Z", "private: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::_Emplace_back_with_unused_capacity<double>(double &&)");
+    test("??$emplace_back@AEBN@?$vector@NV?$allocator@N@std@@@std@@QEAA?A_TAEBN@Z", "public: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::emplace_back<double const &>(double const &)");
+    test("??$_Emplace_back_with_unused_capacity@AEBN@?$vector@NV?$allocator@N@std@@@std@@AEAA?A_TAEBN@Z", "private: decltype(auto) __cdecl std::vector<double, class std::allocator<double>>::_Emplace_back_with_unused_capacity<double const &>(double const &)");
+    test(
+        "??$_To_address@PEAN@std@@YA?A_PAEBQEAN@Z",
+        "auto __cdecl std::_To_address<double *>(double *const &)",
+    );
+    test(
+        "??$_To_address@PEAM@std@@YA?A_PAEBQEAM@Z",
+        "auto __cdecl std::_To_address<float *>(float *const &)",
+    );
+    test(
+        "??$_Get_unwrapped@AEBQEAM@std@@YA?A_TAEBQEAM@Z",
+        "decltype(auto) __cdecl std::_Get_unwrapped<float *const &>(float *const &)",
+    );
+    test(
+        "??$_Get_unwrapped@AEBQEAN@std@@YA?A_TAEBQEAN@Z",
+        "decltype(auto) __cdecl std::_Get_unwrapped<double *const &>(double *const &)",
+    );
+    test("??$emplace_back@AEBH@?$vector@HV?$allocator@H@std@@@std@@QEAA?A_TAEBH@Z", "public: decltype(auto) __cdecl std::vector<int, class std::allocator<int>>::emplace_back<int const &>(int const &)");
+    test("??$_Emplace_back_with_unused_capacity@AEBH@?$vector@HV?$allocator@H@std@@@std@@AEAA?A_TAEBH@Z", "private: decltype(auto) __cdecl std::vector<int, class std::allocator<int>>::_Emplace_back_with_unused_capacity<int const &>(int const &)");
+    test(
+        "??$_Get_unwrapped@AEBQEAH@std@@YA?A_TAEBQEAH@Z",
+        "decltype(auto) __cdecl std::_Get_unwrapped<int *const &>(int *const &)",
+    );
+    test(
+        "??$_To_address@PEAH@std@@YA?A_PAEBQEAH@Z",
+        "auto __cdecl std::_To_address<int *>(int *const &)",
+    );
+    test("??$_Get_unwrapped@AEBQEAV?$vector@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@V?$allocator@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@2@@std@@@std@@YA?A_TAEBQEAV?$vector@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@V?$allocator@V?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@2@@0@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<class std::vector<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>, class std::allocator<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>>> *const &>(class std::vector<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>, class std::allocator<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>>>> *const &)");
+    test("??$_Get_unwrapped@AEBQEAV?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@std@@@std@@YA?A_TAEBQEAV?$vector@UMYRECT@@V?$allocator@UMYRECT@@@std@@@0@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<class std::vector<struct MYRECT, class std::allocator<struct MYRECT>> *const &>(class std::vector<struct MYRECT, class std::allocator<struct MYRECT>> *const &)");
+    test("??$_Get_unwrapped@AEBQEAUMYRECT@@@std@@YA?A_TAEBQEAUMYRECT@@@Z", "decltype(auto) __cdecl std::_Get_unwrapped<struct MYRECT *const &>(struct MYRECT *const &)");
+    test("??$emplace_back@M@?$vector@MV?$allocator@M@std@@@std@@QEAA?A_T$$QEAM@Z", "public: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::emplace_back<float>(float &&)");
+    test("??$_Emplace_back_with_unused_capacity@M@?$vector@MV?$allocator@M@std@@@std@@AEAA?A_T$$QEAM@Z", "private: decltype(auto) __cdecl std::vector<float, class std::allocator<float>>::_Emplace_back_with_unused_capacity<float>(float &&)");
+}
+
+#[test]
+fn test_alloc_preserved_on_failure() {
+    let mut buffer = String::new();
+    buffer.reserve(0x1000);
+    let result = crate::demangle_into("abc", Flags::default(), &mut buffer);
+    debug_assert!(result.is_err());
+    debug_assert!(buffer.capacity() >= 0x1000);
+}
+
+#[test]
+fn test_excess_backrefs() {
+    do_test(
+		"??$prefer_fn@V?$any_executor@U?$context_as_t@AEAVexecution_context@asio@boost@@@execution@asio@boost@@U?$never_t@$0A@@blocking@detail@234@U?$prefer_only@U?$possibly_t@$0A@@blocking@detail@execution@asio@boost@@@234@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$untracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$fork_t@$0A@@relationship@detail@execution@asio@boost@@@234@U?$prefer_only@U?$continuation_t@$0A@@relationship@detail@execution@asio@boost@@@234@@execution@asio@boost@@V?$basic_executor_type@V?$allocator@X@std@@$0A@@io_context@34@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@@any_executor_base@detail@execution@asio@boost@@KA?AV?$any_executor@U?$context_as_t@AEAVexecution_context@asio@boost@@@execution@asio@boost@@U?$never_t@$0A@@blocking@detail@234@U?$prefer_only@U?$possibly_t@$0A@@blocking@detail@execution@asio@boost@@@234@U?$prefer_only@U?$tracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$untracked_t@$0A@@outstanding_work@detail@execution@asio@boost@@@234@U?$prefer_only@U?$fork_t@$0A@@relationship@detail@execution@asio@boost@@@234@U?$prefer_only@U?$continuation_t@$0A@@relationship@detail@execution@asio@boost@@@234@@234@PEBX0@Z",
+		"protected: static class boost::asio::execution::any_executor<struct boost::asio::execution::context_as_t<class boost::asio::execution_context &>, struct boost::asio::execution::detail::blocking::never_t<0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::blocking::possibly_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::untracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::fork_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::continuation_t<0>>> __cdecl boost::asio::execution::detail::any_executor_base::prefer_fn<class boost::asio::execution::any_executor<struct boost::asio::execution::context_as_t<class boost::asio::execution_context &>, struct boost::asio::execution::detail::blocking::never_t<0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::blocking::possibly_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::untracked_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::fork_t<0>>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::relationship::continuation_t<0>>>, class boost::asio::io_context::basic_executor_type<class std::allocator<void>, 0>, struct boost::asio::execution::prefer_only<struct boost::asio::execution::detail::outstanding_work::tracked_t<0>>>(void const *, void const *)",
+		false,
+		Flags::default()
 	);
 }
+
+#[test]
+fn test_classify() {
+    let test_kind = |mangled_name: &str, kind: SymbolKind| {
+        assert_eq!(
+            crate::classify(mangled_name).unwrap(),
+            kind,
+            "{mangled_name}"
+        );
+    };
+
+    test_kind("?world@@YA?AUhello@@XZ", SymbolKind::Function);
+    test_kind("??_9Base@@$B7AA", SymbolKind::Function);
+    test_kind("??__Efoo@@YAXXZ", SymbolKind::Function);
+    test_kind("?x@@3HA", SymbolKind::Variable);
+    test_kind("??_7Base@@6B@", SymbolKind::SpecialTable);
+    test_kind("??_8Middle2@@7B@", SymbolKind::SpecialTable);
+    test_kind("??_R4Base@@6B@", SymbolKind::Rtti);
+    test_kind("??_R0?AUBase@@@8", SymbolKind::Rtti);
+    test_kind("??_R1A@?0A@EA@Base@@8", SymbolKind::Rtti);
+    test_kind("??_R2Base@@8", SymbolKind::Rtti);
+    test_kind("??_R3Base@@8", SymbolKind::Rtti);
+    test_kind(".?AUBase@@", SymbolKind::Rtti);
+    test_kind(
+        "??_C@_05OMLEGLOC@h?$AAi?$AA?$AA?$AA@",
+        SymbolKind::StringLiteral,
+    );
+    test_kind("??@a6a285da2eea70dba6b578022be61d81@", SymbolKind::Md5);
+    test_kind("??__J?1??f@@YAXXZ@51", SymbolKind::ThreadGuard);
+    test_kind("??_B?1??getS@@YAAAUS@@XZ@51", SymbolKind::StaticGuard);
+    assert!(crate::classify("?x@@3").is_err());
+
+    // user names are never mistaken for RTTI, regardless of how they're spelled
+    test_kind("?`RTTI Complete Object Locator'@@3HA", SymbolKind::Variable);
+}
+
+#[test]
+fn test_symbol_info() {
+    let info = crate::symbol_info("??_R0?AUBase@@@8").unwrap();
+    assert_eq!(info.kind, SymbolKind::Rtti);
+    assert_eq!(info.type_descriptor_extra_bytes, None);
+
+    for (mangled_name, extra_bytes) in [
+        ("??_R0?AUBase@@@8A@", 0),
+        ("??_R0?AUBase@@@80", 1),
+        ("??_R0?AUBase@@@8BA@", 16),
+        ("??_R0PEAVklass@@@8BAA@", 256),
+    ] {
+        let info = crate::symbol_info(mangled_name).unwrap();
+        assert_eq!(info.kind, SymbolKind::Rtti, "{mangled_name}");
+        assert_eq!(
+            info.type_descriptor_extra_bytes,
+            Some(extra_bytes),
+            "{mangled_name}"
+        );
+    }
+
+    test("??_R0?AUBase@@@8BA@", "struct Base `RTTI Type Descriptor'");
+    assert_eq!(
+        crate::symbol_info("?x@@3HA")
+            .unwrap()
+            .type_descriptor_extra_bytes,
+        None
+    );
+    assert!(crate::symbol_info("??_R0?AUBase@@@8?BA@").is_err());
+    assert!(crate::symbol_info("??_R0?AUBase@@@8BA@X").is_err());
+}
+
+#[test]
+fn test_variable_name_kind() {
+    let test_variable = |mangled_name: &str, variable_name: Option<VariableNameKind>| {
+        assert_eq!(
+            crate::symbol_info(mangled_name).unwrap().variable_name,
+            variable_name,
+            "{mangled_name}"
+        );
+    };
+
+    test_variable(".?AUBase@@", Some(VariableNameKind::TypeDescriptor));
+    test_variable("?AUBase@@", Some(VariableNameKind::Type));
+    test_variable("_TI1?AVexception@std@@", Some(VariableNameKind::ThrowInfo));
+    test_variable(
+        "_CTA1?AVexception@std@@",
+        Some(VariableNameKind::CatchableTypeArray),
+    );
+    test_variable("?x@ns@@3HA", Some(VariableNameKind::Qualified));
+    test_variable("??_R0?AUBase@@@8", Some(VariableNameKind::Qualified));
+    test_variable("?world@@YAXXZ", None);
+    test_variable("??_7Base@@6B@", None);
+}
+
+#[test]
+fn test_access() {
+    let test_access = |mangled_name: &str, access: Access| {
+        assert_eq!(
+            crate::symbol_info(mangled_name).unwrap().access,
+            access,
+            "{mangled_name}"
+        );
+    };
+
+    test_access("?pub_foo@S@@QAEXXZ", Access::Public);
+    test_access("?prot_foo@S@@IAEXXZ", Access::Protected);
+    test_access("?priv_foo@S@@AAEXXZ", Access::Private);
+    test_access("?static_foo@S@@SAXXZ", Access::Public);
+    test_access("?virtual_foo@S@@MAEXXZ", Access::Protected);
+    test_access("?foo@@YAXXZ", Access::None);
+    test_access("?x@S@@2HA", Access::Public);
+    test_access("?x@S@@1HA", Access::Protected);
+    test_access("?x@S@@0HA", Access::Private);
+    test_access("?x@@3HA", Access::None);
+    test_access("?x@?1??f@@YAXXZ@4HA", Access::None);
+    test_access("??_7Base@@6B@", Access::None);
+}
+
+#[test]
+fn test_function_attributes() {
+    let attributes = |mangled_name: &str| {
+        crate::symbol_info(mangled_name)
+            .unwrap()
+            .function_attributes
+    };
+
+    assert_eq!(
+        attributes("?f@S@@VAEXXZ"),
+        Some(FunctionAttributes {
+            is_far: true,
+            is_virtual: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        attributes("?f@S@@SAXXZ"),
+        Some(FunctionAttributes {
+            is_static: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        attributes("?f@@9"),
+        Some(FunctionAttributes {
+            is_extern_c: true,
+            no_parameter_list: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        attributes("?f@S@@W7EAAXXZ"),
+        Some(FunctionAttributes {
+            is_virtual: true,
+            has_thunk: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(attributes("?f@@YAXXZ"), Some(FunctionAttributes::default()));
+    assert_eq!(attributes("?x@@3HA"), None);
+}
+
+#[test]
+fn test_rtti_kind() {
+    let test_rtti = |mangled_name: &str, rtti: Option<RttiKind>| {
+        assert_eq!(
+            crate::symbol_info(mangled_name).unwrap().rtti,
+            rtti,
+            "{mangled_name}"
+        );
+    };
+
+    test_rtti("??_R0?AUBase@@@8", Some(RttiKind::TypeDescriptor));
+    test_rtti("??_R1A@?0A@EA@Base@@8", Some(RttiKind::BaseClassDescriptor));
+    test_rtti("??_R2Base@@8", Some(RttiKind::BaseClassArray));
+    test_rtti("??_R3Base@@8", Some(RttiKind::ClassHierarchyDescriptor));
+    test_rtti("??_R4Base@@6B@", Some(RttiKind::CompleteObjectLocator));
+    test_rtti(".?AUBase@@", None);
+    test_rtti("??_7Base@@6B@", None);
+    test_rtti("?x@@3HA", None);
+    // user names which merely look like RTTI structures aren't RTTI
+    test_rtti("?`RTTI Class Hierarchy Descriptor'@Base@@3HA", None);
+    test_rtti("?`RTTI Base Class Array'@Base@@2HA", None);
+    test_rtti("?x@@3V`RTTI Type Descriptor'@@A", None);
+}
+
+#[test]
+fn test_three_way_comparison() {
+    let test_comparison = |mangled_name: &str, category: Option<ComparisonCategory>| {
+        assert_eq!(
+            crate::symbol_info(mangled_name)
+                .unwrap()
+                .three_way_comparison,
+            category,
+            "{mangled_name}"
+        );
+    };
+
+    test_comparison(
+        "??__MS@@QEAA?AVstrong_ordering@std@@AEBU0@@Z",
+        Some(ComparisonCategory::StrongOrdering),
+    );
+    test_comparison(
+        "??__MS@@QEBA?AVweak_ordering@std@@AEBU0@@Z",
+        Some(ComparisonCategory::WeakOrdering),
+    );
+    test_comparison(
+        "??__M@YA?AVpartial_ordering@std@@AEBUS@@0@Z",
+        Some(ComparisonCategory::PartialOrdering),
+    );
+    test_comparison("??__MS@@QEAA_NAEBU0@@Z", Some(ComparisonCategory::Other));
+    test_comparison(
+        "??__MS@@QEAA?AVstrong_ordering@foo@@AEBU0@@Z",
+        Some(ComparisonCategory::Other),
+    );
+    test_comparison("??8S@@QEAA_NAEBU0@@Z", None);
+    test_comparison("?x@@3HA", None);
+}
+
+#[test]
+fn test_structor_kind() {
+    let test_structor = |mangled_name: &str, kind: Option<StructorKind>, class: Option<&str>| {
+        let info = crate::symbol_info(mangled_name).unwrap();
+        assert_eq!(info.structor_kind, kind, "{mangled_name}");
+        assert_eq!(info.structor_class.as_deref(), class, "{mangled_name}");
+    };
+
+    test_structor(
+        "??0klass@@QEAA@XZ",
+        Some(StructorKind::Constructor),
+        Some("klass"),
+    );
+    test_structor(
+        "??1klass@@QEAA@XZ",
+        Some(StructorKind::Destructor),
+        Some("klass"),
+    );
+    test_structor(
+        "??0klass@ns@@QEAA@AEBV01@@Z",
+        Some(StructorKind::Constructor),
+        Some("ns::klass"),
+    );
+    test_structor(
+        "??1?$vector@HV?$allocator@H@std@@@std@@QEAA@XZ",
+        Some(StructorKind::Destructor),
+        Some("std::vector<int, class std::allocator<int>>"),
+    );
+    test_structor("??_Gklass@@UEAAPEAXI@Z", None, None);
+    test_structor("?func@klass@@QEAAXXZ", None, None);
+    test_structor("?x@@3HA", None, None);
+}
+
+#[test]
+fn test_profile_instrumentation() {
+    test(
+        "__profd_?foo@@YAXXZ",
+        "[profile data] void __cdecl foo(void)",
+    );
+    test(
+        "__profc_?bar@ns@@YAHH@Z",
+        "[profile counter] int __cdecl ns::bar(int)",
+    );
+    test(
+        "__profvp_??0klass@@QEAA@XZ",
+        "[profile value] public: __cdecl klass::klass(void)",
+    );
+    do_test(
+        "__profd_?foo@@YAXXZ",
+        "[profile data] foo",
+        false,
+        Flags::NAME_ONLY,
+    );
+    for mangled_name in [
+        "__profc_?foo@@YAXXZ",
+        "__profd_?foo@@YAXXZ",
+        "__profvp_??0klass@@QEAA@XZ",
+    ] {
+        assert_eq!(
+            crate::classify(mangled_name).unwrap(),
+            SymbolKind::Variable,
+            "{mangled_name}"
+        );
+    }
+    assert!(crate::demangle("__profd_", Flags::default()).is_err());
+    assert!(crate::demangle("__profd___profd_?foo@@YAXXZ", Flags::default()).is_err());
+}
+
+#[test]
+fn test_demangle_fmt() {
+    struct Unwritable;
+
+    impl fmt::Write for Unwritable {
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    for mangled_name in [
+        "?x@@3PEAVty@@EA",
+        "?func@MyClass@@UEAAHHH@Z",
+        "??$WrapFnPtr@$1?VoidFn@@YAXXZ@@YAXXZ",
+        "?M@?1??L@@YAHXZ@4HA",
+        "__profd_?foo@@YAXXZ",
+    ] {
+        let mut result = String::new();
+        crate::demangle_fmt(mangled_name, Flags::default(), &mut result).unwrap();
+        assert_eq!(
+            result,
+            crate::demangle(mangled_name, Flags::default()).unwrap()
+        );
+    }
+
+    let result = crate::demangle_fmt("?x@@3HA", Flags::default(), &mut Unwritable);
+    assert!(matches!(result, Err(crate::Error::Fmt(_))));
+    assert!(crate::demangle_fmt("?x@@3", Flags::default(), &mut String::new()).is_err());
+}
+
+#[test]
+fn test_demangle_to() {
+    let mangled_name = "?func@MyClass@@UEAAHHH@Z";
+    let demangled_name = "public: virtual int __cdecl MyClass::func(int, int)";
+
+    let result = crate::demangle_to(mangled_name, Flags::default(), String::new()).unwrap();
+    assert_eq!(result, demangled_name);
+
+    let mut string = String::from("garbage");
+    crate::demangle_to(mangled_name, Flags::default(), &mut string).unwrap();
+    assert_eq!(string, demangled_name);
+
+    let mut bytes = b"garbage".to_vec();
+    crate::demangle_to(mangled_name, Flags::default(), &mut bytes).unwrap();
+    assert_eq!(bytes, demangled_name.as_bytes());
+
+    let mut stream = b"prefix: ".to_vec();
+    crate::demangle_to(
+        mangled_name,
+        Flags::default(),
+        &mut stream as &mut dyn io::Write,
+    )
+    .unwrap();
+    assert_eq!(stream, format!("prefix: {demangled_name}").as_bytes());
+
+    let mut bytes = Vec::with_capacity(0x1000);
+    bytes.extend_from_slice(b"garbage");
+    assert!(crate::demangle_to("abc", Flags::default(), &mut bytes).is_err());
+    assert!(bytes.is_empty());
+    assert!(bytes.capacity() >= 0x1000);
+}
+
+#[test]
+fn test_constant_pool() {
+    test("__real@40490fdb", "float 3.1415927");
+    test("__real@3f800000", "float 1.0");
+    test("__real@bf000000", "float -0.5");
+    test("__real@400921fb54442d18", "double 3.141592653589793");
+    test("__real@3ff0000000000000", "double 1.0");
+    test("__real@7ff0000000000000", "double inf");
+    test(
+        "__xmm@000000000000000000000000000000ff",
+        "__m128 0x000000000000000000000000000000ff",
+    );
+    test(
+        "__ymm@3f8000003f8000003f8000003f8000003f8000003f8000003f8000003f800000",
+        "__m256 0x3f8000003f8000003f8000003f8000003f8000003f8000003f8000003f800000",
+    );
+    do_test(
+        "__real@3ff0000000000000",
+        "1.0",
+        false,
+        Flags::NO_VARIABLE_TYPE,
+    );
+    assert_eq!(
+        crate::classify("__real@3ff0000000000000").unwrap(),
+        SymbolKind::Variable
+    );
+
+    for mangled_name in [
+        "__real@",
+        "__real@3ff",
+        "__real@3ff00000000000000",
+        "__real@3ff000000000000g",
+        "__xmm@00",
+        "__ymm@000000000000000000000000000000ff",
+    ] {
+        assert!(
+            crate::demangle(mangled_name, Flags::default()).is_err(),
+            "{mangled_name}"
+        );
+    }
+}
+
+#[test]
+fn test_malicious_output() {
+    // each template argument list repeats its first argument via a backref, which
+    // grows the output exponentially with the nesting depth
+    let mut nested = String::from("V?$a@H@@");
+    for _ in 0..6 {
+        nested = format!("V?$t@{nested}{}@@", "V1@".repeat(9));
+    }
+    let mangled_name = format!("?x@@3{nested}A");
+
+    let result = crate::demangle(&mangled_name, Flags::default());
+    assert!(matches!(result, Err(crate::Error::MaliciousInput)));
+    let result = crate::demangle_to(&mangled_name, Flags::default(), &mut Vec::new());
+    assert!(matches!(result, Err(crate::Error::MaliciousInput)));
+    let result = crate::demangle_fmt(&mangled_name, Flags::default(), &mut String::new());
+    assert!(matches!(result, Err(crate::Error::MaliciousInput)));
+}
+
+#[test]
+fn test_rust_symbols() {
+    test(
+        "_RNvCs1234_7mycrate4main",
+        "[rust] _RNvCs1234_7mycrate4main",
+    );
+    test(
+        "_R0NvCs1234_7mycrate4main",
+        "[rust] _R0NvCs1234_7mycrate4main",
+    );
+    test(
+        "_ZN4core3fmt5write17h0123456789abcdefE",
+        "[rust] _ZN4core3fmt5write17h0123456789abcdefE",
+    );
+    assert_eq!(
+        crate::classify("_RNvCs1234_7mycrate4main").unwrap(),
+        SymbolKind::Foreign
+    );
+    test(
+        "_RINvNtC3std3mem8align_ofdEB2_",
+        "[rust] _RINvNtC3std3mem8align_ofdEB2_",
+    );
+    test(
+        "_RNvMs_NtCs1234_7mycrate3fooNtB4_3Bar3new",
+        "[rust] _RNvMs_NtCs1234_7mycrate3fooNtB4_3Bar3new",
+    );
+    test(
+        "_RNvCs1234_7mycrate4main.llvm.1234",
+        "[rust] _RNvCs1234_7mycrate4main.llvm.1234",
+    );
+    // C symbols and Itanium C++ symbols which happen to share a prefix are left alone
+    assert!(crate::demangle("_RC4_set_key", Flags::default()).is_err());
+    assert!(crate::demangle("_RIPEMD160", Flags::default()).is_err());
+    assert!(crate::demangle("_RNvCs1234_7mycrate", Flags::default()).is_err());
+    assert!(crate::demangle("_RNvCs1234_7mycrate4mainX", Flags::default()).is_err());
+    assert!(crate::demangle("_RtlUnwind@16", Flags::default()).is_err());
+    assert!(crate::demangle("_ZN3foo3barEv", Flags::default()).is_err());
+    assert!(crate::demangle("_ZN1E", Flags::default()).is_err());
+    assert!(crate::demangle("_R", Flags::default()).is_err());
+    assert!(crate::demangle("_RNvCs1234_7mycrate4main", Flags::LLVM_COMPAT).is_err());
+}
+
+#[test]
+fn test_objc_symbols() {
+    test("+[NSObject alloc]", "[objc] +[NSObject alloc]");
+    test(
+        "-[MyView initWithFrame:style:]",
+        "[objc] -[MyView initWithFrame:style:]",
+    );
+    test("_OBJC_CLASS_$_MyView", "[objc] _OBJC_CLASS_$_MyView");
+    test(
+        "_OBJC_METACLASS_$_MyView",
+        "[objc] _OBJC_METACLASS_$_MyView",
+    );
+    do_test(
+        "-[MyView layout]",
+        "[objc] -[MyView layout]",
+        false,
+        Flags::NAME_ONLY,
+    );
+    assert_eq!(
+        crate::classify("-[MyView layout]").unwrap(),
+        SymbolKind::Foreign
+    );
+    // only whole symbols are passed through
+    assert!(crate::demangle("?x@?1?+[@3HA", Flags::default()).is_err());
+}
+
+#[test]
+fn test_producer_hint() {
+    let test_producer = |mangled_name: &str, producer: Producer| {
+        assert_eq!(
+            crate::producer_hint(mangled_name),
+            producer,
+            "{mangled_name}"
+        );
+    };
+
+    test_producer("?a@@3HA", Producer::Msvc);
+    test_producer("??_7Base@@6B@", Producer::Msvc);
+    test_producer("??@a6a285da2eea70dba6b578022be61d81@", Producer::Msvc);
+    test_producer("?f@Atomic@@YAXU?$_Atomic@H@__clang@@@Z", Producer::ClangCl);
+    test_producer("?f@Float16@@YAXU_Float16@__clang@@@Z", Producer::ClangCl);
+    test_producer(
+        "?f@@YAXPEAU?$_ASCLglobal@$$CAH@__clang@@@Z",
+        Producer::ClangCl,
+    );
+    test_producer("_ZN5hello5worldEv", Producer::Unknown);
+    test_producer("+[NSObject alloc]", Producer::Unknown);
+    test_producer("?f@@YAX", Producer::Unknown);
+}
+
+#[test]
+fn test_type_names() {
+    test("?AV?$ClassA@VClassB@@@@", "class ClassA<class ClassB>");
+    test("?AUfoo@bar@@", "struct bar::foo");
+    test("?AW4E@@", "enum E");
+    test("?ATU@@", "union U");
+    test(
+        "?AV?$vector@HV?$allocator@H@std@@@std@@",
+        "class std::vector<int, class std::allocator<int>>",
+    );
+    assert_eq!(
+        crate::classify("?AV?$ClassA@VClassB@@@@").unwrap(),
+        SymbolKind::Rtti
+    );
+
+    // symbols whose name happens to look like the start of a type
+    test("?AVX@@3HA", "int AVX");
+    test("?AVX@Y@@2HA", "public: static int Y::AVX");
+    assert!(crate::demangle("?AVX@@Z", Flags::default()).is_err());
+}
+
+#[test]
+fn test_template_arguments() {
+    let test_args = |mangled_name: &str, flags: Flags, args: Option<&[&str]>| {
+        let result = crate::template_arguments(mangled_name, flags).unwrap();
+        let result = result
+            .as_ref()
+            .map(|x| x.iter().map(String::as_str).collect::<Vec<_>>());
+        assert_eq!(result.as_deref(), args, "{mangled_name}");
+    };
+
+    test_args(
+        "??$foo@H@space@@YAABHABH@Z",
+        Flags::default(),
+        Some(&["int"]),
+    );
+    test_args(
+        "??$variadic_fn_template@HHD$$BY01D@@YAXABH0ABDAAY01$$CBD@Z",
+        Flags::default(),
+        Some(&["int", "int", "char", "char[2]"]),
+    );
+    test_args(
+        "??0?$vector@HV?$allocator@H@std@@@std@@QEAA@XZ",
+        Flags::NO_TAG_SPECIFIER,
+        Some(&["int", "std::allocator<int>"]),
+    );
+    test_args(
+        "??1?$vector@H@std@@QEAA@XZ",
+        Flags::default(),
+        Some(&["int"]),
+    );
+    // only the arguments of the unqualified name are returned
+    test_args(
+        "??$Foo@H@?$BoolTemplate@$00@@QEAAXH@Z",
+        Flags::default(),
+        Some(&["int"]),
+    );
+    test_args("?bar@?$vector@H@std@@QEAAXXZ", Flags::default(), None);
+    test_args(
+        "??$templ_fun_with_pack@$S@@YAXXZ",
+        Flags::default(),
+        Some(&[]),
+    );
+    test_args("?a@@3HA", Flags::default(), None);
+    assert!(crate::template_arguments("??$foo@H", Flags::default()).is_err());
+}
+
+#[test]
+fn test_operator_spacing() {
+    // operator names ending in punctuation are directly followed by their
+    // parameter list or template arguments
+    test("??Cklass@@QEAAHXZ", "int __cdecl klass::operator->(void)");
+    test("??Oklass@@QEAAHH@Z", "int __cdecl klass::operator>(int)");
+    test("??Pklass@@QEAAHH@Z", "int __cdecl klass::operator>=(int)");
+    test("??5klass@@QEAAHH@Z", "int __cdecl klass::operator>>(int)");
+    test("??Jklass@@QEAAHH@Z", "int __cdecl klass::operator->*(int)");
+    test("??Aklass@@QEAAHH@Z", "int __cdecl klass::operator[](int)");
+    test("??Rklass@@QEAAHH@Z", "int __cdecl klass::operator()(int)");
+    test("??O@YA_NHH@Z", "bool __cdecl operator>(int, int)");
+    test("??$?OH@@YA_NHH@Z", "bool __cdecl operator><int>(int, int)");
+    test(
+        "??$f@$1??Cklass@@QEAAHXZ@@YAXXZ",
+        "void __cdecl f<&public: int __cdecl klass::operator->(void)>(void)",
+    );
+    test(
+        "??$f@$1??Oklass@@QEAAHH@Z@@YAXXZ",
+        "void __cdecl f<&public: int __cdecl klass::operator>(int)>(void)",
+    );
+}
+
+#[test]
+fn test_backref_overflow() {
+    // only the first 10 names can be back-referenced, the rest are not memorized
+    test(
+        "?f@@YAXUa@@Ub@@Uc@@Ud@@Ue@@Uf@@Ug@@Uh@@Ui@@Uj@@Uk@@Ul@@U9@U0@@Z",
+        "void __cdecl f(struct a, struct b, struct c, struct d, struct e, struct f, struct g, struct h, struct i, struct j, struct k, struct l, struct j, struct f)",
+    );
+    test(
+        "?x@l@k@j@i@h@g@f@e@d@c@b@a@@3U9@A",
+        "struct d a::b::c::d::e::f::g::h::i::j::k::l::x",
+    );
+    test(
+        "?x@l@k@j@i@h@g@f@e@d@c@b@a@@3Ul@@A",
+        "struct l a::b::c::d::e::f::g::h::i::j::k::l::x",
+    );
+}
+
+#[test]
+fn test_exception_tables() {
+    test(
+        "_TI1?AVexception@std@@",
+        "`throw info for class std::exception'",
+    );
+    test("_TI1H", "`throw info for int'");
+    test("_TIC1?AVe@@", "`throw info for class e const'");
+    test("_TICV1?AUS@@", "`throw info for struct S const volatile'");
+    test("_TIC2PAH", "`throw info for int const *'");
+    test("_TIC2PEAH", "`throw info for int const *'");
+    test(
+        "_TI3?AVruntime_error@std@@",
+        "`throw info for class std::runtime_error'",
+    );
+    test(
+        "_CTA1?AVexception@std@@",
+        "`catchable type array for class std::exception'",
+    );
+    test("_CTA2PAD", "`catchable type array for char *'");
+    test("_CTA12?AVe@@", "`catchable type array for class e'");
+    assert_eq!(
+        crate::classify("_TI1?AVexception@std@@").unwrap(),
+        SymbolKind::SpecialTable
+    );
+
+    for mangled_name in ["_TI", "_TI?AVe@@", "_TI1?AVe@@X", "_CTA", "_CTA1"] {
+        assert!(
+            crate::demangle(mangled_name, Flags::default()).is_err(),
+            "{mangled_name}"
+        );
+    }
+}
+
+#[test]
+fn test_cli_generics() {
+    test(
+        "?f@@YAXV?$$HList@H@@@Z",
+        "void __cdecl f(class [generic] List<int>)",
+    );
+    test(
+        "?f@@YAXV?$$HList@H@@V1@@Z",
+        "void __cdecl f(class [generic] List<int>, class [generic] List<int>)",
+    );
+    test(
+        "?g@?$$HBox@M@@QAEXXZ",
+        "public: void __thiscall [generic] Box<float>::g(void)",
+    );
+    test(
+        "?f@@YAXV?$List@V?$$HBox@H@@@@@Z",
+        "void __cdecl f(class List<class [generic] Box<int>>)",
+    );
+    // native templates whose names merely begin with $H
+    test(
+        "?f@@YAXV?$$Hello@$0A@@@@Z",
+        "void __cdecl f(class $Hello<0>)",
+    );
+    test(
+        "?f@@YAXV?$$Hello@$1?g@@3HA@@@Z",
+        "void __cdecl f(class $Hello<&int g>)",
+    );
+    test(
+        "?f@@YAXV?$$H?0List@H@@@Z",
+        "void __cdecl f(class $H?0List<int>)",
+    );
+}
+
+#[test]
+fn test_llvm_compat() {
+    // the llvm test corpus renders identically
+    for mangled_name in [
+        "?mbb@S@@QAEX_N0@Z",
+        "??$?HH@S@@QEAAAEAU0@H@Z",
+        "?foo@L@PR13207@@QAEXV?$I@VA@PR13207@@@2@@Z",
+        "??0klass@@QEAA@XZ",
+        "??Bklass@@QEAAHXZ",
+        "??_C@_1BA@KFOBIOMM@?$AAh?$AAe?$AAl?$AAl?$AAo?$AA?$AA@",
+        "?d@foo@@0FB",
+        "??@a6a285da2eea70dba6b578022be61d81@",
+        ".?AVfoo@@",
+    ] {
+        assert_eq!(
+            crate::demangle(mangled_name, Flags::LLVM_COMPAT).unwrap(),
+            crate::demangle(mangled_name, Flags::default()).unwrap(),
+            "{mangled_name}"
+        );
+    }
+
+    // but extensions are rejected
+    for mangled_name in [
+        "+[NSObject alloc]",
+        "_OBJC_CLASS_$_NSObject",
+        "__profc_?f@@YAXXZ",
+        "?AVfoo@@",
+        "_TI1?AVexception@std@@",
+        "_CTA1?AVexception@std@@",
+        "__real@3ff0000000000000",
+        "__imp_load_?foo@@YAXXZ",
+        "__tailMerge_KERNEL32_dll",
+    ] {
+        assert!(
+            crate::demangle(mangled_name, Flags::LLVM_COMPAT).is_err(),
+            "{mangled_name}"
+        );
+    }
+
+    do_test(
+        "?f@@YAXV?$$HList@H@@@Z",
+        "void __cdecl f(class $HList<int>)",
+        false,
+        Flags::LLVM_COMPAT,
+    );
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn test_dump_backrefs() {
+    let backrefs = crate::dump_backrefs("?f@ns@@YAXPAUS@1@0@Z", Flags::default()).unwrap();
+    assert_eq!(backrefs.names, ["f", "ns", "S"]);
+    assert_eq!(backrefs.function_params, ["struct ns::S *"]);
+
+    let backrefs =
+        crate::dump_backrefs("?x@a@b@c@d@e@f@g@h@i@j@k@l@@3HA", Flags::default()).unwrap();
+    assert_eq!(backrefs.names.len(), crate::MAX_NAME_BACKREFS);
+    assert_eq!(backrefs.names.last().unwrap(), "i");
+
+    let backrefs = crate::dump_backrefs(
+        "?f@@YAXPAUa@@PAUb@@PAUc@@PAUd@@PAUe@@PAUf@@PAUg@@PAUh@@PAUi@@PAUj@@PAUk@@@Z",
+        Flags::default(),
+    )
+    .unwrap();
+    assert_eq!(backrefs.function_params.len(), crate::MAX_PARAM_BACKREFS);
+    assert_eq!(backrefs.function_params.last().unwrap(), "struct j *");
+
+    assert!(crate::dump_backrefs("?f@@YAX", Flags::default()).is_err());
+}
+
+#[test]
+fn test_function_type_variables() {
+    test("?x@@3$$A6AHXZA", "int __cdecl x(void)");
+    test("?x@@3$$A6AHHH@ZA", "int __cdecl x(int, int)");
+    test(
+        "?x@ns@@2$$A6AHXZA",
+        "public: static int __cdecl ns::x(void)",
+    );
+    test("?x@@3$$A8@@BAHXZA", "int __cdecl x(void) const");
+    test("?x@@3$$A8@@IGAAHXZA", "int __cdecl x(void) __restrict &");
+    do_test("?x@@3$$A6AHXZA", "x", false, Flags::NO_VARIABLE_TYPE);
+}
+
+#[test]
+fn test_demangle_all_into() {
+    let mut results: Vec<_> = (0..4).map(|_| String::with_capacity(64)).collect();
+    let failures = crate::demangle_all_into(
+        ["?world@@YA?AUhello@@XZ", "?x@@3HA"],
+        Flags::default(),
+        &mut results,
+    );
+    assert_eq!(failures, 0);
+    assert_eq!(results, ["struct hello __cdecl world(void)", "int x"]);
+    assert!(results[1].capacity() >= 64);
+
+    let failures = crate::demangle_all_into(
+        ["?x@@3", "?y@@3DA", "??", "?z@@3MA"],
+        Flags::NAME_ONLY,
+        &mut results,
+    );
+    assert_eq!(failures, 2);
+    assert_eq!(results, ["", "y", "", "z"]);
+
+    let failures = crate::demangle_all_into([], Flags::default(), &mut results);
+    assert_eq!(failures, 0);
+    assert!(results.is_empty());
+}
+
+#[test]
+fn test_delay_imports() {
+    test(
+        "__imp_load_?foo@@YAXXZ",
+        "[delay import] void __cdecl foo(void)",
+    );
+    test(
+        "__imp_load_??0klass@@QEAA@XZ",
+        "[delay import] public: __cdecl klass::klass(void)",
+    );
+    test("__imp_load_?x@@3HA", "[delay import] int x");
+    test("__imp_load_CreateFileW", "[delay import] CreateFileW");
+    test(
+        "__tailMerge_KERNEL32_dll",
+        "[delay import] __tailMerge_KERNEL32_dll",
+    );
+    test("__delayLoadHelper2", "[delay import] __delayLoadHelper2");
+    test(
+        "__DELAY_IMPORT_DESCRIPTOR_KERNEL32_dll",
+        "[delay import] __DELAY_IMPORT_DESCRIPTOR_KERNEL32_dll",
+    );
+    do_test(
+        "__imp_load_?foo@@YAXXZ",
+        "[delay import] foo",
+        false,
+        Flags::NAME_ONLY,
+    );
+
+    assert_eq!(
+        crate::classify("__imp_load_?foo@@YAXXZ").unwrap(),
+        SymbolKind::Function
+    );
+    assert_eq!(
+        crate::classify("__imp_load_CreateFileW").unwrap(),
+        SymbolKind::Foreign
+    );
+    assert!(crate::demangle("__imp_load_", Flags::default()).is_err());
+    assert!(crate::demangle("__imp_load_?foo@@YAX", Flags::default()).is_err());
+}
+
+#[test]
+fn test_thread_safe_statics() {
+    test(
+        "?$TSS0@?1??f@@YAXXZ@4HA",
+        "int `void __cdecl f(void)'::`2'::`thread-safe static init counter'",
+    );
+    test(
+        "?$TSS1@?1??getInstance@Foo@@SAAEAV2@XZ@4HA",
+        "int `public: static class Foo & __cdecl Foo::getInstance(void)'::`2'::`thread-safe static init counter'{1}",
+    );
+    test("?$TSSx@@3HA", "int $TSSx");
+    // only function local statics are guard counters
+    test("?x@@3V$TSS1@@A", "class $TSS1 x");
+    test("?x@$TSS1@@3HA", "int $TSS1::x");
+    test("?$TSS1@@3HA", "int $TSS1");
+    test(
+        "_Init_thread_header",
+        "[thread-safe static] _Init_thread_header",
+    );
+    test(
+        "_Init_thread_footer",
+        "[thread-safe static] _Init_thread_footer",
+    );
+    test(
+        "__Init_thread_abort",
+        "[thread-safe static] __Init_thread_abort",
+    );
+    do_test(
+        "?$TSS0@?1??f@@YAXXZ@4HA",
+        "int `void __cdecl f(void)'::`2'::$TSS0",
+        false,
+        Flags::LLVM_COMPAT,
+    );
+
+    assert_eq!(
+        crate::classify("?$TSS0@?1??f@@YAXXZ@4HA").unwrap(),
+        SymbolKind::ThreadGuard
+    );
+    assert_eq!(
+        crate::classify("?$TSS1@@3HA").unwrap(),
+        SymbolKind::Variable
+    );
+    assert_eq!(
+        crate::classify("_Init_thread_header").unwrap(),
+        SymbolKind::Foreign
+    );
+}
+
+#[test]
+fn test_demangle_stream() {
+    let input = "?world@@YA?AUhello@@XZ\r\n??\n\n?x@@3HA";
+    let mut output = Vec::new();
+    crate::demangle_stream(input.as_bytes(), &mut output, Flags::NAME_ONLY).unwrap();
+    assert_eq!(output, b"world\r\n??\n\nx");
+
+    // line endings are preserved as is, even when they're mixed
+    let mut output = Vec::new();
+    crate::demangle_stream(
+        &b"?x@@3HA\r\n?y@@3HA\n?z@@3HA\r\n"[..],
+        &mut output,
+        Flags::default(),
+    )
+    .unwrap();
+    assert_eq!(output, b"int x\r\nint y\nint z\r\n");
+
+    let mut output = Vec::new();
+    crate::demangle_stream(&b""[..], &mut output, Flags::default()).unwrap();
+    assert!(output.is_empty());
+
+    // lines which aren't valid UTF-8 are passed through, without interrupting the rest of the stream
+    let mut output = Vec::new();
+    crate::demangle_stream(
+        &b"?x@@3HA\n\xFF?y@@3HA\r\n?y@@3HA"[..],
+        &mut output,
+        Flags::default(),
+    )
+    .unwrap();
+    assert_eq!(output, b"int x\n\xFF?y@@3HA\r\nint y");
+}
+
+#[test]
+fn test_udt_returning() {
+    test(
+        "??_P?Hklass@@QAE?AV0@ABV0@@Z",
+        "public: class klass __thiscall klass::`udt returning'operator+(class klass const &)",
+    );
+    test(
+        "??_Pmake@@YA?AUS@@XZ",
+        "struct S __cdecl `udt returning'make(void)",
+    );
+    test(
+        "??_P?$make@H@@YA?AUS@@XZ",
+        "struct S __cdecl `udt returning'make<int>(void)",
+    );
+    test(
+        "??_Pfoo@ns@@YA?AUS@1@XZ",
+        "struct ns::S __cdecl ns::`udt returning'foo(void)",
+    );
+    do_test(
+        "??_Pmake@@YA?AUS@@XZ",
+        "`udt returning'make",
+        false,
+        Flags::NAME_ONLY,
+    );
+    assert!(matches!(
+        crate::demangle("??_P?0klass@@QAE@XZ", Flags::default()),
+        Err(crate::Error::InvalidUdtReturning)
+    ));
+}
+
+#[test]
+fn test_namespace_path() {
+    let test_path = |mangled_name: &str, path: &[&str]| {
+        assert_eq!(
+            crate::namespace_path(mangled_name).unwrap(),
+            path,
+            "{mangled_name}"
+        );
+    };
+
+    test_path(
+        "?spam@NB@PR13207@@YAXV?$Y@VX@NA@PR13207@@@NA@2@@Z",
+        &["PR13207", "NB"],
+    );
+    test_path("?foo@?$klass@H@ns@@QEAAXXZ", &["ns", "klass<int>"]);
+    test_path("??0klass@@QEAA@XZ", &["klass"]);
+    test_path("?M@?1??L@@YAHXZ@4HA", &["`int __cdecl L(void)'::`2'"]);
+    test_path("?x@?A0x12345678@ns@@3HA", &["ns", "`anonymous namespace'"]);
+    test_path("?world@@YAXXZ", &[]);
+    test_path("??_C@_02PCEFGMJL@hi?$AA@", &[]);
+    test_path("+[NSObject alloc]", &[]);
+    assert!(crate::namespace_path("?x@@3").is_err());
+}
+
+#[test]
+fn test_array_of_function_types() {
+    test(
+        "??$f@$$BY04$$A6AHXZ@@YAXXZ",
+        "void __cdecl f<int (__cdecl[5])(void)>(void)",
+    );
+    test(
+        "??$f@$$BY04P6AHXZ@@YAXXZ",
+        "void __cdecl f<int (__cdecl *[5])(void)>(void)",
+    );
+    test("?x@@3PAY04$$A6AHXZA", "int (__cdecl (*x)[5])(void)");
+    do_test(
+        "??$f@$$BY04$$A6AHXZ@@YAXXZ",
+        "void f<int ([5])(void)>(void)",
+        false,
+        Flags::NO_CALLING_CONVENTION,
+    );
+}
+
+#[test]
+fn test_demangle_with_seeds() {
+    let test_seeds = |mangled_name: &str, seeds: &[&str], demangled_name: &str| {
+        assert_eq!(
+            crate::demangle_with_seeds(mangled_name, Flags::default(), seeds).unwrap(),
+            demangled_name,
+            "{mangled_name}"
+        );
+    };
+
+    test_seeds("?f@0@YAXXZ", &["ns"], "void __cdecl ns::f(void)");
+    test_seeds(
+        "?f@10@YAXXZ",
+        &["ns", "klass"],
+        "void __cdecl ns::klass::f(void)",
+    );
+    test_seeds("?f@@YAXPAU1@@Z", &["ns", "S"], "void __cdecl f(struct S *)");
+    // names in the symbol are memorized after the seeds
+    test_seeds(
+        "?f@ns@@YAXPAU1@@Z",
+        &["S"],
+        "void __cdecl ns::f(struct f *)",
+    );
+    // duplicate seeds are only memorized once
+    test_seeds(
+        "?f@1@YAXXZ",
+        &["ns", "ns", "klass"],
+        "void __cdecl klass::f(void)",
+    );
+    test_seeds("?world@@YAXXZ", &[], "void __cdecl world(void)");
+    assert!(crate::demangle_with_seeds("?f@1@YAXXZ", Flags::default(), &[]).is_err());
+}
+
+#[test]
+fn test_demangle_with_capacity() {
+    let input = "?world@@YA?AUhello@@XZ";
+    let result = crate::demangle_with_capacity(input, Flags::default(), 0x100).unwrap();
+    assert_eq!(result, "struct hello __cdecl world(void)");
+    assert!(result.capacity() >= 0x100);
+
+    let result = crate::demangle_with_capacity(input, Flags::default(), 0).unwrap();
+    assert_eq!(result, "struct hello __cdecl world(void)");
+    assert!(
+        crate::demangle_with_capacity("?world@@YA?AUhello@@", Flags::default(), 0x100).is_err()
+    );
+}
+
+#[test]
+fn test_runtime_helpers() {
+    for name in [
+        "__guard_dispatch_icall_fptr",
+        "__guard_check_icall_fptr",
+        "__guard_fids_table",
+        "__guard_eh_cont_table",
+        "__guard_eh_cont_count",
+        "_guard_dispatch_icall",
+        "_guard_check_icall_nop",
+        "__chkstk",
+        "_alloca_probe_16",
+        "__security_check_cookie",
+        "__security_cookie",
+        "__GSHandlerCheck_EH4",
+        "__CxxFrameHandler4",
+    ] {
+        test(name, &format!("[runtime helper] {name}"));
+        assert_eq!(crate::classify(name).unwrap(), SymbolKind::Foreign);
+        assert!(crate::demangle(name, Flags::LLVM_COMPAT).is_err());
+    }
+
+    // user symbols which merely share a prefix with a runtime helper
+    for name in [
+        "_guard_foo_user",
+        "__guard_my_table",
+        "__security_context",
+        "__chkstk_mine",
+        "__CxxFrameHandlerWrapper",
+    ] {
+        assert!(crate::demangle(name, Flags::default()).is_err(), "{name}");
+    }
+}
+
+#[test]
+fn test_overload_key() {
+    let test_key = |mangled_name: &str, key: &str| {
+        assert_eq!(
+            crate::overload_key(mangled_name, Flags::default())
+                .unwrap()
+                .unwrap(),
+            key,
+            "{mangled_name}"
+        );
+    };
+
+    test_key("?world@@YAXXZ", "world(0)");
+    test_key("?foo@@YAXI@Z", "foo(1)");
+    test_key("?foo@ns@@YAXHH@Z", "ns::foo(2)");
+    test_key("?foo@ns@@YAXMN@Z", "ns::foo(2)");
+    test_key("?func@MyClass@@UEAAHHH@Z", "MyClass::func(2)");
+    test_key("?printf@@YAHPEBDZZ", "printf(1, ...)");
+    test_key("?f@@YAXZZ", "f(...)");
+    test_key("??$f@H@@YAXH@Z", "f<int>(1)");
+    test_key("??Hklass@@QEAAHH@Z", "klass::operator+(1)");
+    test_key("??1klass@@QEAA@XZ", "klass::~klass(0)");
+    assert_eq!(
+        crate::overload_key("?x@@3HA", Flags::default()).unwrap(),
+        None
+    );
+    assert!(crate::overload_key("?world@@YAX", Flags::default()).is_err());
+}
+
+#[test]
+fn test_rvalue_reference_returns() {
+    test("?f@@YA$$QEAHXZ", "int && __cdecl f(void)");
+    test("?f@@YA$$QEBHXZ", "int const && __cdecl f(void)");
+    test("?f@@YA$$QAUS@@XZ", "struct S && __cdecl f(void)");
+    test(
+        "??$forward@H@std@@YA$$QEAHAEAH@Z",
+        "int && __cdecl std::forward<int>(int &)",
+    );
+    test(
+        "?get@klass@@QEHAA$$QEAHXZ",
+        "public: int && __cdecl klass::get(void) &&",
+    );
+    test("?f@@YA$$REAHXZ", "int &&volatile __cdecl f(void)");
+    test("?f@@YAX$$RAH@Z", "void __cdecl f(int &&volatile)");
+}
+
+#[test]
+fn test_leaf_name() {
+    let test_leaf = |mangled_name: &str, leaf: &str| {
+        assert_eq!(
+            crate::leaf_name(mangled_name).unwrap(),
+            leaf,
+            "{mangled_name}"
+        );
+    };
+
+    test_leaf("?spam@NB@PR13207@@YAXV?$Y@VX@NA@PR13207@@@NA@2@@Z", "spam");
+    test_leaf("?x@ns@@3HA", "x");
+    test_leaf("??Hklass@@QEAAHH@Z", "operator+");
+    test_leaf("??Bklass@@QEAAHXZ", "operator int");
+    test_leaf("??0klass@@QEAA@XZ", "klass");
+    test_leaf("??1klass@@QEAA@XZ", "~klass");
+    test_leaf("??1?$klass@H@ns@@QEAA@XZ", "~klass<int>");
+    test_leaf("??$f@H@ns@@YAXH@Z", "f<int>");
+    test_leaf("?foo@?$klass@H@ns@@QEAAXXZ", "foo");
+    test_leaf("?M@?1??L@@YAHXZ@4HA", "M");
+    test_leaf("??_7Base@@6B@", "`vftable'");
+    test_leaf("??_C@_02PCEFGMJL@hi?$AA@", "");
+    assert!(crate::leaf_name("?x@@3").is_err());
+}
+
+#[test]
+fn test_truncated_qualifiers() {
+    for mangled_name in [
+        // variable
+        "?x@@3H",
+        // pointer
+        "?x@@3PEAH",
+        "?x@@3PEAHE",
+        // member pointer
+        "?x@@3PEQklass@@H",
+        "?x@@3P8klass@@E",
+        // function pointer
+        "?x@@3P6AXXZ",
+        // array
+        "?f@@YAXPAY01$$C",
+        // member function
+        "?f@klass@@QE",
+        // special table
+        "??_7Base@@6",
+    ] {
+        assert!(crate::validate(mangled_name, Flags::default()).is_err());
+        assert!(
+            matches!(
+                crate::demangle(mangled_name, Flags::default()),
+                Err(crate::Error::InvalidQualifiers)
+            ),
+            "{mangled_name}"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let info = crate::symbol_info("??1?$klass@H@ns@@QEAA@XZ").unwrap();
+    let json = serde_json::to_string(&info).unwrap();
+    assert_eq!(
+        json,
+        r#"{"kind":"Function","type_descriptor_extra_bytes":null,"three_way_comparison":null,"structor_kind":"Destructor","structor_class":"ns::klass<int>","variable_name":null,"access":"Public","function_attributes":{"is_far":false,"is_static":false,"is_virtual":false,"is_extern_c":false,"no_parameter_list":false,"has_thunk":false},"rtti":null}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<crate::SymbolInfo>(&json).unwrap(),
+        info
+    );
+
+    for mangled_name in [
+        ".?AUBase@@",
+        "??_R0?AUBase@@@8BA@",
+        "??__MS@@QEAA?AVstrong_ordering@std@@AEBU0@@Z",
+    ] {
+        let info = crate::symbol_info(mangled_name).unwrap();
+        let json = serde_json::to_string(&info).unwrap();
+        assert_eq!(
+            serde_json::from_str::<crate::SymbolInfo>(&json).unwrap(),
+            info,
+            "{mangled_name}"
+        );
+    }
+
+    let producer = crate::producer_hint("?world@@YA?AUhello@@XZ");
+    let json = serde_json::to_string(&producer).unwrap();
+    assert_eq!(json, r#""Msvc""#);
+    assert_eq!(
+        serde_json::from_str::<crate::Producer>(&json).unwrap(),
+        producer
+    );
+}
+
+#[test]
+fn test_vector_intrinsics() {
+    let test = |mangled_name: &str, demangled_name: &str| {
+        do_test(mangled_name, demangled_name, false, Flags::default());
+    };
+
+    test("?f@@YAXT__m64@@@Z", "void __cdecl f(__m64)");
+    test("?f@@YAXT__m128@@@Z", "void __cdecl f(__m128)");
+    test(
+        "?f@@YA?AT__m256@@AEBU__m128d@@T__m128i@@@Z",
+        "__m256 __cdecl f(__m128d const &, __m128i)",
+    );
+    test(
+        "?f@@YAXU__m256d@@T__m256i@@@Z",
+        "void __cdecl f(__m256d, __m256i)",
+    );
+    test("?f@@YAXPEAT__m512@@@Z", "void __cdecl f(__m512 *)");
+
+    // only the canonical global names are recognized
+    test("?f@@YAXT__m128x@@@Z", "void __cdecl f(union __m128x)");
+    test("?f@@YAXT__m128@ns@@@Z", "void __cdecl f(union ns::__m128)");
+}
+
+#[test]
+fn test_function_type_descriptor_names() {
+    test(
+        ".?AP6AHXZ",
+        "int (__cdecl *)(void) `RTTI Type Descriptor Name'",
+    );
+    test(
+        ".?AP6AHH@Z",
+        "int (__cdecl *)(int) `RTTI Type Descriptor Name'",
+    );
+    test(
+        ".?A$$A6AHXZ",
+        "int __cdecl(void) `RTTI Type Descriptor Name'",
+    );
+    test(
+        ".?AP8S@@AEHXZ",
+        "int (__thiscall S::*)(void) `RTTI Type Descriptor Name'",
+    );
+    do_test(
+        ".?AP6AHXZ",
+        "int (__cdecl *)(void)",
+        false,
+        Flags::NAME_ONLY,
+    );
+}
+
+#[test]
+fn test_demangle_pair() {
+    let input = String::from("?x@ns@@3HA");
+    let (mangled, demangled) = crate::demangle_pair(&input, Flags::NO_VARIABLE_TYPE).unwrap();
+    assert!(std::ptr::eq(mangled, input.as_str()));
+    assert_eq!(demangled, "ns::x");
+    assert!(crate::demangle_pair("?x@ns@@3H", Flags::default()).is_err());
+
+    assert_eq!(
+        crate::demangle_annotated("?x@ns@@3HA", Flags::default()).unwrap(),
+        "int ns::x [?x@ns@@3HA]"
+    );
+    assert!(crate::demangle_annotated("?x@ns@@3H", Flags::default()).is_err());
+}
+
+#[test]
+fn test_structor_back_references() {
+    test(
+        "??0?$A@H@0@QAE@XZ",
+        "public: __thiscall A<int>::A<int>::A<int>(void)",
+    );
+    test(
+        "??1?$A@H@0@QAE@XZ",
+        "public: __thiscall A<int>::A<int>::~A<int>(void)",
+    );
+    test("??0B@A@1@QAE@XZ", "public: __thiscall A::A::B::B(void)");
+    assert_eq!(
+        crate::symbol_info("??0?$A@H@0@QAE@XZ")
+            .unwrap()
+            .structor_class
+            .as_deref(),
+        Some("A<int>::A<int>")
+    );
+
+    // the class is the first name in the symbol, so only a seeded name can be referenced
+    assert!(matches!(
+        crate::demangle("??00@QAE@XZ", Flags::default()),
+        Err(crate::Error::InvalidBackRef)
+    ));
+    assert_eq!(
+        crate::demangle_with_seeds("??00@QAE@XZ", Flags::default(), &["klass"]).unwrap(),
+        "public: __thiscall klass::klass(void)"
+    );
+    assert_eq!(
+        crate::demangle_with_seeds("??11@QAE@XZ", Flags::default(), &["ns", "klass"]).unwrap(),
+        "public: __thiscall klass::~klass(void)"
+    );
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_demangle_with_stats() {
+    let stats = |mangled_name: &str| {
+        let (result, stats) = crate::demangle_with_stats(mangled_name, Flags::default()).unwrap();
+        assert_eq!(
+            result,
+            crate::demangle(mangled_name, Flags::default()).unwrap()
+        );
+        assert_eq!(stats.output_len, result.len());
+        stats
+    };
+
+    let simple = stats("?x@@3HA");
+    assert_eq!(simple.max_depth, 1);
+    assert_eq!(simple.max_name_backrefs, 1);
+    assert_eq!(simple.max_param_backrefs, 0);
+
+    let nested = stats("?x@@3PEAPEAPEAHEA");
+    assert_eq!(nested.max_depth, 4);
+    assert!(nested.node_count > simple.node_count);
+
+    let backrefs = stats("?f@@YAXPAH0PAD1PAF2@Z");
+    assert_eq!(backrefs.max_param_backrefs, 3);
+
+    assert!(crate::demangle_with_stats("?x@@3H", Flags::default()).is_err());
+}